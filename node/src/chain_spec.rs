@@ -1,9 +1,13 @@
 use hex_literal::hex;
 use node_bridge_runtime::{
-	constants::currency::{JUR_ASSET_ID, JUR_DECIMALS, JUR_MINIMUM_BALANCE, JUR_NAME, JUR_SYMBOL},
+	constants::currency::{
+		JUR_ASSET_ID, JUR_CURRENCY_CODE, JUR_DECIMALS, JUR_MINIMUM_BALANCE, JUR_NAME, JUR_SYMBOL,
+	},
+	pallet_bridge::helpers::ExternalCurrency,
 	AccountId, AssetsConfig, AuraConfig, BalancesConfig, BridgeConfig, GenesisConfig,
 	GrandpaConfig, Signature, SudoConfig, SystemConfig, WASM_BINARY,
 };
+use primitives::crypto::Public as RelayerId;
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{ecdsa, Pair, Public};
@@ -53,7 +57,11 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				vec![authority_keys_from_seed("Alice")],
 				// Sudo account
 				get_account_id_from_seed::<ecdsa::Public>("Alice"),
-				vec![AccountId::from(hex!("3Cd0A705a2DC65e5b1E1205896BaA2be8A07c6e0"))],
+				// Relayers, with the relayer key `--alice` generates
+				vec![
+					get_account_id_from_seed::<RelayerId>("Alice"),
+					AccountId::from(hex!("3Cd0A705a2DC65e5b1E1205896BaA2be8A07c6e0")),
+				],
 				// Pre-funded accounts
 				vec![
 					get_account_id_from_seed::<ecdsa::Public>("Alice"),
@@ -94,7 +102,11 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
 				// Sudo account
 				get_account_id_from_seed::<ecdsa::Public>("Alice"),
-				vec![AccountId::from(hex!("3Cd0A705a2DC65e5b1E1205896BaA2be8A07c6e0"))],
+				// Relayers, with the relayer key `--alice` generates
+				vec![
+					get_account_id_from_seed::<RelayerId>("Alice"),
+					AccountId::from(hex!("3Cd0A705a2DC65e5b1E1205896BaA2be8A07c6e0")),
+				],
 				// Pre-funded accounts
 				vec![
 					get_account_id_from_seed::<ecdsa::Public>("Alice"),
@@ -144,6 +156,12 @@ fn testnet_genesis(
 	)];
 	let assets = vec![(JUR_ASSET_ID, root_key, true, JUR_MINIMUM_BALANCE)];
 	let endowed_assets = Vec::with_capacity(endowed_accounts.len());
+	// Relayers bond `RelayerMinStake` at genesis
+	let funded_relayers: Vec<_> = relayers
+		.iter()
+		.filter(|relayer| !endowed_accounts.contains(relayer))
+		.cloned()
+		.collect();
 	GenesisConfig {
 		system: SystemConfig {
 			// Add Wasm runtime to storage.
@@ -151,7 +169,12 @@ fn testnet_genesis(
 		},
 		balances: BalancesConfig {
 			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+			balances: endowed_accounts
+				.iter()
+				.chain(&funded_relayers)
+				.cloned()
+				.map(|k| (k, 1 << 60))
+				.collect(),
 		},
 		assets: AssetsConfig { assets, accounts: endowed_assets, metadata },
 		aura: AuraConfig {
@@ -165,6 +188,17 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
-		bridge: BridgeConfig { relayers },
+		bridge: BridgeConfig {
+			relayers,
+			relayer_threshold: 1,
+			currencies: vec![(
+				ExternalCurrency {
+					code: JUR_CURRENCY_CODE,
+					// Development issuer account on the external ledger
+					issuer: hex!("4dbE9bA2A2Bd1bF8bcF9d2E1b4E1B44C6cB4dB5E").into(),
+				},
+				JUR_ASSET_ID,
+			)],
+		},
 	}
 }
@@ -0,0 +1,763 @@
+//! Keystore client of an external signer, so that no key is stored on the node's disk.
+//!
+//! `--keystore-uri` is either `unix:///path/to/signer.sock` or `tcp://127.0.0.1:9955`. Requests
+//! are neither authenticated nor encrypted, so `tcp://` only accepts loopback addresses; reach
+//! a signer on another host through an authenticated tunnel. Each request is a single line of
+//! JSON on a new connection, and the signer answers with a single line holding either a
+//! `result` or an `error`:
+//!
+//! ```text
+//! {"method":"public_keys","key_type":"brdg","scheme":"ecdsa"}
+//! {"result":["0x02..."]}
+//! ```
+//!
+//! Keys and signatures are `0x` prefixed hex. Key types are their four characters. `sign`
+//! follows `sign_with` of substrate's keystores, so ecdsa keys sign the blake2-256 hash of the
+//! message, while `sign_prehashed` signs the digest as given.
+//!
+//! The public keys of each key type and scheme are listed once and kept in memory, so only
+//! signing and key generation reach the signer afterwards. Keys added on the signer directly
+//! are picked up after a restart of the node. Requests block the calling thread for at most
+//! `TIMEOUT`, the async `CryptoStore` methods run them on the blocking thread pool.
+
+use std::{
+	collections::HashMap,
+	io::{self, BufRead, BufReader, Read, Write},
+	net::{SocketAddr, TcpStream},
+	os::unix::net::UnixStream,
+	path::PathBuf,
+	sync::{Arc, PoisonError, RwLock},
+	time::Duration,
+};
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_core::{
+	crypto::{ByteArray, CryptoTypeId, CryptoTypePublicPair, KeyTypeId},
+	ecdsa, ed25519, sr25519, Bytes,
+};
+use sp_keystore::{
+	vrf::{VRFSignature, VRFTranscriptData},
+	CryptoStore, Error, SyncCryptoStore,
+};
+
+const LOG_TARGET: &str = "remote-keystore";
+/// Time the signer has to accept and answer a request, well below the 6 seconds slot time.
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Signature scheme of a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scheme {
+	/// Schnorrkel sr25519 keys.
+	Sr25519,
+	/// Ed25519 keys.
+	Ed25519,
+	/// Secp256k1 ECDSA keys.
+	Ecdsa,
+}
+
+impl Scheme {
+	fn from_crypto_id(id: KeyTypeId, crypto_id: CryptoTypeId) -> Result<Self, Error> {
+		match crypto_id {
+			sr25519::CRYPTO_ID => Ok(Scheme::Sr25519),
+			ed25519::CRYPTO_ID => Ok(Scheme::Ed25519),
+			ecdsa::CRYPTO_ID => Ok(Scheme::Ecdsa),
+			_ => Err(Error::KeyNotSupported(id)),
+		}
+	}
+
+	/// Crypto type of the keys of this scheme.
+	pub fn crypto_id(self) -> CryptoTypeId {
+		match self {
+			Scheme::Sr25519 => sr25519::CRYPTO_ID,
+			Scheme::Ed25519 => ed25519::CRYPTO_ID,
+			Scheme::Ecdsa => ecdsa::CRYPTO_ID,
+		}
+	}
+}
+
+/// Request to the signer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum Request {
+	/// Public keys of a key type, as a list.
+	PublicKeys {
+		/// Key type.
+		key_type: String,
+		/// Scheme of the keys listed.
+		scheme: Scheme,
+	},
+	/// Generate a key, from `seed` if any, and return its public key.
+	Generate {
+		/// Key type.
+		key_type: String,
+		/// Scheme of the key generated.
+		scheme: Scheme,
+		/// Secret URI of the key.
+		seed: Option<String>,
+	},
+	/// Insert a key of unknown scheme, returns `null`.
+	Insert {
+		/// Key type.
+		key_type: String,
+		/// Secret URI of the key.
+		suri: String,
+		/// Public key of the key.
+		public: Bytes,
+	},
+	/// Sign `message`, returns `null` if the signer has no such key.
+	///
+	/// Ecdsa keys sign the blake2-256 hash of `message`.
+	Sign {
+		/// Key type.
+		key_type: String,
+		/// Scheme of the key.
+		scheme: Scheme,
+		/// Public key of the key signing.
+		public: Bytes,
+		/// Message signed.
+		message: Bytes,
+	},
+	/// Sign a 32 bytes digest with an ecdsa key, returns `null` if the signer has no such key.
+	SignPrehashed {
+		/// Key type.
+		key_type: String,
+		/// Public key of the key signing.
+		public: Bytes,
+		/// Digest signed.
+		message: Bytes,
+	},
+}
+
+/// Answer of the signer, either `result` or `error`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+	/// Result of the request.
+	#[serde(default)]
+	pub result: serde_json::Value,
+	/// Why the request failed.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Endpoint {
+	Tcp(SocketAddr),
+	Unix(PathBuf),
+}
+
+/// Public keys listed by the signer, by key type and scheme.
+type KeyCache = HashMap<(KeyTypeId, Scheme), Vec<Bytes>>;
+
+/// Keystore forwarding signing and key generation to an external signer.
+///
+/// Keys are neither generated nor stored on the node, the signer holds them all. Sr25519 VRF
+/// signatures are not supported, which no consensus of this node requires.
+#[derive(Debug, Clone)]
+pub struct RemoteKeystore {
+	endpoint: Endpoint,
+	keys: Arc<RwLock<KeyCache>>,
+}
+
+impl RemoteKeystore {
+	/// Keystore of the signer at `uri`, connected to on each request.
+	pub fn open(uri: &str) -> Result<Self, String> {
+		let endpoint = if let Some(address) = uri.strip_prefix("tcp://") {
+			match address.parse::<SocketAddr>() {
+				Ok(address) if address.ip().is_loopback() => Endpoint::Tcp(address),
+				_ => return Err(format!("keystore address {address} is not a loopback address")),
+			}
+		} else if let Some(path) = uri.strip_prefix("unix://") {
+			Endpoint::Unix(path.into())
+		} else {
+			return Err(format!("unsupported keystore URI {uri}, expected tcp:// or unix://"))
+		};
+		Ok(Self { endpoint, keys: Default::default() })
+	}
+
+	fn call<R: DeserializeOwned>(&self, request: &Request) -> Result<R, Error> {
+		let mut line =
+			serde_json::to_vec(request).map_err(|error| Error::Other(error.to_string()))?;
+		line.push(b'\n');
+		let answer = match &self.endpoint {
+			Endpoint::Tcp(address) =>
+				TcpStream::connect_timeout(address, TIMEOUT).and_then(|stream| {
+					stream.set_read_timeout(Some(TIMEOUT))?;
+					stream.set_write_timeout(Some(TIMEOUT))?;
+					exchange(stream, &line)
+				}),
+			Endpoint::Unix(path) => UnixStream::connect(path).and_then(|stream| {
+				stream.set_read_timeout(Some(TIMEOUT))?;
+				stream.set_write_timeout(Some(TIMEOUT))?;
+				exchange(stream, &line)
+			}),
+		}
+		.map_err(|error| {
+			log::warn!(target: LOG_TARGET, "signer unavailable: {}", error);
+			Error::Unavailable
+		})?;
+		let response: Response = serde_json::from_str(&answer)
+			.map_err(|error| Error::Other(format!("invalid signer response: {error}")))?;
+		if let Some(error) = response.error {
+			return Err(Error::Other(error))
+		}
+		serde_json::from_value(response.result)
+			.map_err(|error| Error::Other(format!("invalid signer result: {error}")))
+	}
+
+	/// Run `f` on the blocking thread pool, off the async executor.
+	async fn blocking<R: Send + 'static>(&self, f: impl FnOnce(&Self) -> R + Send + 'static) -> R {
+		let keystore = self.clone();
+		tokio::task::spawn_blocking(move || f(&keystore))
+			.await
+			.unwrap_or_else(|error| std::panic::resume_unwind(error.into_panic()))
+	}
+
+	/// Public keys of a key type and scheme, only listed by the signer the first time.
+	fn listed_keys(&self, id: KeyTypeId, scheme: Scheme) -> Result<Vec<Bytes>, Error> {
+		let cache = self.keys.read().unwrap_or_else(PoisonError::into_inner);
+		if let Some(keys) = cache.get(&(id, scheme)) {
+			return Ok(keys.clone())
+		}
+		drop(cache);
+		let keys: Vec<Bytes> =
+			self.call(&Request::PublicKeys { key_type: key_type(id), scheme })?;
+		self.keys
+			.write()
+			.unwrap_or_else(PoisonError::into_inner)
+			.insert((id, scheme), keys.clone());
+		Ok(keys)
+	}
+
+	fn public_keys<P: ByteArray>(&self, id: KeyTypeId, scheme: Scheme) -> Vec<P> {
+		match self.listed_keys(id, scheme) {
+			Ok(keys) => keys.iter().filter_map(|key| P::from_slice(key).ok()).collect(),
+			Err(error) => {
+				log::warn!(target: LOG_TARGET, "failed to list {:?} keys: {}", id, error);
+				Vec::new()
+			},
+		}
+	}
+
+	fn generate<P: ByteArray>(
+		&self,
+		id: KeyTypeId,
+		scheme: Scheme,
+		seed: Option<&str>,
+	) -> Result<P, Error> {
+		let request =
+			Request::Generate { key_type: key_type(id), scheme, seed: seed.map(Into::into) };
+		let public: Bytes = self.call(&request)?;
+		let key = P::from_slice(&public).map_err(|_| Error::Other("invalid public key".into()))?;
+		let mut cache = self.keys.write().unwrap_or_else(PoisonError::into_inner);
+		if let Some(keys) = cache.get_mut(&(id, scheme)) {
+			if !keys.contains(&public) {
+				keys.push(public);
+			}
+		}
+		Ok(key)
+	}
+}
+
+fn exchange<S: Read + Write>(mut stream: S, request: &[u8]) -> io::Result<String> {
+	stream.write_all(request)?;
+	let mut answer = String::new();
+	BufReader::new(stream).read_line(&mut answer)?;
+	Ok(answer)
+}
+
+fn key_type(id: KeyTypeId) -> String {
+	String::from_utf8_lossy(&id.0).into_owned()
+}
+
+impl SyncCryptoStore for RemoteKeystore {
+	fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		self.public_keys(id, Scheme::Sr25519)
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, Error> {
+		self.generate(id, Scheme::Sr25519, seed)
+	}
+
+	fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		self.public_keys(id, Scheme::Ed25519)
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, Error> {
+		self.generate(id, Scheme::Ed25519, seed)
+	}
+
+	fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys(id, Scheme::Ecdsa)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, Error> {
+		self.generate(id, Scheme::Ecdsa, seed)
+	}
+
+	fn insert_unknown(&self, id: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		let request = Request::Insert {
+			key_type: key_type(id),
+			suri: suri.into(),
+			public: public.to_vec().into(),
+		};
+		self.call::<()>(&request).map_err(|error| {
+			log::warn!(target: LOG_TARGET, "failed to insert a {:?} key: {}", id, error);
+		})?;
+		// The scheme of the key is unknown, list all the keys of its type again
+		self.keys
+			.write()
+			.unwrap_or_else(PoisonError::into_inner)
+			.retain(|(key_id, _), _| *key_id != id);
+		Ok(())
+	}
+
+	fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		let all_keys = SyncCryptoStore::keys(self, id)?;
+		Ok(keys.into_iter().filter(|key| all_keys.contains(key)).collect())
+	}
+
+	fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		[Scheme::Sr25519, Scheme::Ed25519, Scheme::Ecdsa]
+			.into_iter()
+			.map(|scheme| {
+				let keys = self.listed_keys(id, scheme)?;
+				Ok(keys.into_iter().map(move |key| CryptoTypePublicPair(scheme.crypto_id(), key.0)))
+			})
+			.collect::<Result<Vec<_>, Error>>()
+			.map(|keys| keys.into_iter().flatten().collect())
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		public_keys.iter().all(|(public, id)| {
+			[Scheme::Sr25519, Scheme::Ed25519, Scheme::Ecdsa].into_iter().any(|scheme| {
+				self.listed_keys(*id, scheme)
+					.map(|keys| keys.iter().any(|key| key.0 == *public))
+					.unwrap_or(false)
+			})
+		})
+	}
+
+	fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> Result<Option<Vec<u8>>, Error> {
+		let request = Request::Sign {
+			key_type: key_type(id),
+			scheme: Scheme::from_crypto_id(id, key.0)?,
+			public: key.1.clone().into(),
+			message: msg.to_vec().into(),
+		};
+		Ok(self.call::<Option<Bytes>>(&request)?.map(|signature| signature.0))
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &sr25519::Public,
+		_transcript_data: VRFTranscriptData,
+	) -> Result<Option<VRFSignature>, Error> {
+		Err(Error::Other("VRF signatures are not supported by the remote keystore".into()))
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, Error> {
+		let request = Request::SignPrehashed {
+			key_type: key_type(id),
+			public: public.to_raw_vec().into(),
+			message: msg.to_vec().into(),
+		};
+		self.call::<Option<Bytes>>(&request)?
+			.map(|signature| {
+				<[u8; 65]>::try_from(&signature[..])
+					.map(ecdsa::Signature::from_raw)
+					.map_err(|_| Error::Other("invalid ecdsa signature".into()))
+			})
+			.transpose()
+	}
+}
+
+#[async_trait]
+impl CryptoStore for RemoteKeystore {
+	async fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		self.blocking(move |keystore| SyncCryptoStore::sr25519_public_keys(keystore, id))
+			.await
+	}
+
+	async fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, Error> {
+		let seed = seed.map(String::from);
+		self.blocking(move |keystore| {
+			SyncCryptoStore::sr25519_generate_new(keystore, id, seed.as_deref())
+		})
+		.await
+	}
+
+	async fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		self.blocking(move |keystore| SyncCryptoStore::ed25519_public_keys(keystore, id))
+			.await
+	}
+
+	async fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, Error> {
+		let seed = seed.map(String::from);
+		self.blocking(move |keystore| {
+			SyncCryptoStore::ed25519_generate_new(keystore, id, seed.as_deref())
+		})
+		.await
+	}
+
+	async fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.blocking(move |keystore| SyncCryptoStore::ecdsa_public_keys(keystore, id))
+			.await
+	}
+
+	async fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, Error> {
+		let seed = seed.map(String::from);
+		self.blocking(move |keystore| {
+			SyncCryptoStore::ecdsa_generate_new(keystore, id, seed.as_deref())
+		})
+		.await
+	}
+
+	async fn insert_unknown(&self, id: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		let (suri, public) = (suri.to_owned(), public.to_vec());
+		self.blocking(move |keystore| SyncCryptoStore::insert_unknown(keystore, id, &suri, &public))
+			.await
+	}
+
+	async fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		self.blocking(move |keystore| SyncCryptoStore::supported_keys(keystore, id, keys))
+			.await
+	}
+
+	async fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, Error> {
+		self.blocking(move |keystore| SyncCryptoStore::keys(keystore, id)).await
+	}
+
+	async fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let public_keys = public_keys.to_vec();
+		self.blocking(move |keystore| SyncCryptoStore::has_keys(keystore, &public_keys))
+			.await
+	}
+
+	async fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> Result<Option<Vec<u8>>, Error> {
+		let (key, msg) = (key.clone(), msg.to_vec());
+		self.blocking(move |keystore| SyncCryptoStore::sign_with(keystore, id, &key, &msg))
+			.await
+	}
+
+	/// Answered without reaching the signer.
+	async fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		transcript_data: VRFTranscriptData,
+	) -> Result<Option<VRFSignature>, Error> {
+		SyncCryptoStore::sr25519_vrf_sign(self, key_type, public, transcript_data)
+	}
+
+	async fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, Error> {
+		let (public, msg) = (*public, *msg);
+		self.blocking(move |keystore| {
+			SyncCryptoStore::ecdsa_sign_prehashed(keystore, id, &public, &msg)
+		})
+		.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use primitives::{crypto::KEY_TYPE, signature::BridgeSigner};
+	use sp_core::Pair;
+	use sp_keystore::testing::KeyStore;
+	use sp_runtime::traits::{IdentifyAccount, Verify};
+	use std::{net::TcpListener, sync::Arc, thread};
+
+	const AURA: KeyTypeId = KeyTypeId(*b"aura");
+
+	/// Stand-in signer serving requests on a local port from an in-memory keystore
+	fn serve(keystore: Arc<KeyStore>) -> RemoteKeystore {
+		serve_requests(keystore, usize::MAX)
+	}
+
+	/// Stand-in signer going away after `limit` requests
+	fn serve_requests(keystore: Arc<KeyStore>, limit: usize) -> RemoteKeystore {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let uri = format!("tcp://{}", listener.local_addr().unwrap());
+		thread::spawn(move || {
+			for stream in listener.incoming().take(limit) {
+				let mut stream = stream.unwrap();
+				let mut line = String::new();
+				BufReader::new(&stream).read_line(&mut line).unwrap();
+				let response = match handle(&keystore, serde_json::from_str(&line).unwrap()) {
+					Ok(result) => Response { result, error: None },
+					Err(error) => Response { error: Some(error.to_string()), ..Default::default() },
+				};
+				let mut answer = serde_json::to_vec(&response).unwrap();
+				answer.push(b'\n');
+				stream.write_all(&answer).unwrap();
+			}
+		});
+		RemoteKeystore::open(&uri).unwrap()
+	}
+
+	fn handle(keystore: &KeyStore, request: Request) -> Result<serde_json::Value, Error> {
+		let id = |key_type: &str| KeyTypeId::try_from(key_type).unwrap();
+		let result = match request {
+			Request::PublicKeys { key_type, scheme } => {
+				let keys: Vec<Bytes> = match scheme {
+					Scheme::Sr25519 =>
+						SyncCryptoStore::sr25519_public_keys(keystore, id(&key_type))
+							.iter()
+							.map(|key| key.to_raw_vec().into())
+							.collect(),
+					Scheme::Ed25519 =>
+						SyncCryptoStore::ed25519_public_keys(keystore, id(&key_type))
+							.iter()
+							.map(|key| key.to_raw_vec().into())
+							.collect(),
+					Scheme::Ecdsa => SyncCryptoStore::ecdsa_public_keys(keystore, id(&key_type))
+						.iter()
+						.map(|key| key.to_raw_vec().into())
+						.collect(),
+				};
+				serde_json::to_value(keys)
+			},
+			Request::Generate { key_type, scheme, seed } => {
+				let (id, seed) = (id(&key_type), seed.as_deref());
+				let public = match scheme {
+					Scheme::Sr25519 =>
+						SyncCryptoStore::sr25519_generate_new(keystore, id, seed)?.to_raw_vec(),
+					Scheme::Ed25519 =>
+						SyncCryptoStore::ed25519_generate_new(keystore, id, seed)?.to_raw_vec(),
+					Scheme::Ecdsa =>
+						SyncCryptoStore::ecdsa_generate_new(keystore, id, seed)?.to_raw_vec(),
+				};
+				serde_json::to_value(Bytes(public))
+			},
+			Request::Insert { key_type, suri, public } => {
+				SyncCryptoStore::insert_unknown(keystore, id(&key_type), &suri, &public)
+					.map_err(|_| Error::Other("insert failed".into()))?;
+				serde_json::to_value(())
+			},
+			Request::Sign { key_type, scheme, public, message } => {
+				let key = CryptoTypePublicPair(scheme.crypto_id(), public.0);
+				let signature =
+					SyncCryptoStore::sign_with(keystore, id(&key_type), &key, &message)?;
+				serde_json::to_value(signature.map(Bytes))
+			},
+			Request::SignPrehashed { key_type, public, message } => {
+				let public = ecdsa::Public::from_slice(&public).unwrap();
+				let message: [u8; 32] = message.0.try_into().unwrap();
+				let signature = SyncCryptoStore::ecdsa_sign_prehashed(
+					keystore,
+					id(&key_type),
+					&public,
+					&message,
+				)?;
+				serde_json::to_value(signature.map(|signature| Bytes(signature.0.to_vec())))
+			},
+		};
+		Ok(result.unwrap())
+	}
+
+	#[test]
+	fn generates_and_lists_keys_of_each_scheme() {
+		let signer = Arc::new(KeyStore::new());
+		let remote = serve(signer.clone());
+
+		let sr25519_key =
+			SyncCryptoStore::sr25519_generate_new(&remote, AURA, Some("//Alice")).unwrap();
+		let ed25519_key =
+			SyncCryptoStore::ed25519_generate_new(&remote, AURA, Some("//Alice")).unwrap();
+		let ecdsa_key = SyncCryptoStore::ecdsa_generate_new(&remote, KEY_TYPE, None).unwrap();
+
+		assert_eq!(sr25519_key, sr25519::Pair::from_string("//Alice", None).unwrap().public());
+		assert_eq!(SyncCryptoStore::sr25519_public_keys(&*signer, AURA), vec![sr25519_key]);
+		assert_eq!(SyncCryptoStore::sr25519_public_keys(&remote, AURA), vec![sr25519_key]);
+		assert_eq!(SyncCryptoStore::ed25519_public_keys(&remote, AURA), vec![ed25519_key]);
+		assert_eq!(SyncCryptoStore::ecdsa_public_keys(&remote, KEY_TYPE), vec![ecdsa_key]);
+		assert!(SyncCryptoStore::ecdsa_public_keys(&remote, AURA).is_empty());
+		assert_eq!(
+			SyncCryptoStore::keys(&remote, AURA).unwrap(),
+			vec![
+				CryptoTypePublicPair(sr25519::CRYPTO_ID, sr25519_key.to_raw_vec()),
+				CryptoTypePublicPair(ed25519::CRYPTO_ID, ed25519_key.to_raw_vec()),
+			]
+		);
+		assert!(SyncCryptoStore::has_keys(
+			&remote,
+			&[(sr25519_key.to_raw_vec(), AURA), (ecdsa_key.to_raw_vec(), KEY_TYPE)]
+		));
+		assert!(!SyncCryptoStore::has_keys(&remote, &[(ecdsa_key.to_raw_vec(), AURA)]));
+	}
+
+	#[test]
+	fn signs_with_each_scheme() {
+		let remote = serve(Arc::new(KeyStore::new()));
+		let message = b"message";
+
+		let sr25519_key = SyncCryptoStore::sr25519_generate_new(&remote, AURA, None).unwrap();
+		let signature = SyncCryptoStore::sign_with(&remote, AURA, &sr25519_key.into(), message)
+			.unwrap()
+			.unwrap();
+		let signature = sr25519::Signature::try_from(&signature[..]).unwrap();
+		assert!(sr25519::Pair::verify(&signature, message, &sr25519_key));
+
+		let ed25519_key = SyncCryptoStore::ed25519_generate_new(&remote, AURA, None).unwrap();
+		let signature = SyncCryptoStore::sign_with(&remote, AURA, &ed25519_key.into(), message)
+			.unwrap()
+			.unwrap();
+		let signature = ed25519::Signature::try_from(&signature[..]).unwrap();
+		assert!(ed25519::Pair::verify(&signature, message, &ed25519_key));
+
+		let ecdsa_key = SyncCryptoStore::ecdsa_generate_new(&remote, AURA, None).unwrap();
+		let signature = SyncCryptoStore::sign_with(&remote, AURA, &ecdsa_key.into(), message)
+			.unwrap()
+			.unwrap();
+		let signature = ecdsa::Signature::from_raw(signature.try_into().unwrap());
+		assert!(ecdsa::Pair::verify(&signature, message, &ecdsa_key));
+
+		// Keys the signer does not hold
+		let other = sr25519::Pair::from_string("//Bob", None).unwrap().public();
+		assert_eq!(
+			SyncCryptoStore::sign_with(&remote, AURA, &other.into(), message).unwrap(),
+			None
+		);
+	}
+
+	#[test]
+	fn relayer_keys_sign_bridge_signatures() {
+		let signer = Arc::new(KeyStore::new());
+		let remote = serve(signer.clone());
+		let public =
+			SyncCryptoStore::ecdsa_generate_new(&*signer, KEY_TYPE, Some("//Alice")).unwrap();
+		let relayer = BridgeSigner::from(public);
+
+		assert_eq!(primitives::crypto::signers(&remote), vec![relayer.clone()]);
+		let signature = primitives::crypto::sign(&remote, &relayer, b"payload").unwrap();
+		assert!(signature.verify(&b"payload"[..], &relayer.into_account()));
+	}
+
+	#[test]
+	fn inserts_keys() {
+		let signer = Arc::new(KeyStore::new());
+		let remote = serve(signer.clone());
+		let public = sr25519::Pair::from_string("//Alice", None).unwrap().public();
+
+		SyncCryptoStore::insert_unknown(&remote, AURA, "//Alice", public.as_ref()).unwrap();
+		assert_eq!(SyncCryptoStore::sr25519_public_keys(&*signer, AURA), vec![public]);
+	}
+
+	#[test]
+	fn lists_public_keys_once() {
+		let signer = Arc::new(KeyStore::new());
+		// Generating the key and listing them
+		let remote = serve_requests(signer.clone(), 2);
+		let public = SyncCryptoStore::ecdsa_generate_new(&remote, KEY_TYPE, None).unwrap();
+		assert_eq!(SyncCryptoStore::ecdsa_public_keys(&remote, KEY_TYPE), vec![public]);
+		// Give the signer thread time to close the listener
+		thread::sleep(Duration::from_millis(50));
+
+		assert_eq!(SyncCryptoStore::ecdsa_public_keys(&remote, KEY_TYPE), vec![public]);
+		assert!(SyncCryptoStore::has_keys(&remote, &[(public.to_raw_vec(), KEY_TYPE)]));
+		assert!(matches!(
+			SyncCryptoStore::sign_with(&remote, KEY_TYPE, &public.into(), b"message"),
+			Err(Error::Unavailable)
+		));
+	}
+
+	#[test]
+	fn fails_without_signer() {
+		// Nothing listens on the port of a dropped listener
+		let address = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+		let remote = RemoteKeystore::open(&format!("tcp://{address}")).unwrap();
+		let public = sr25519::Pair::from_string("//Alice", None).unwrap().public();
+
+		assert!(SyncCryptoStore::sr25519_public_keys(&remote, AURA).is_empty());
+		assert!(matches!(
+			SyncCryptoStore::sign_with(&remote, AURA, &public.into(), b"message"),
+			Err(Error::Unavailable)
+		));
+		assert!(!SyncCryptoStore::has_keys(&remote, &[(public.to_raw_vec(), AURA)]));
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn signs_off_the_async_executor() {
+		let remote = serve(Arc::new(KeyStore::new()));
+		let public = CryptoStore::ecdsa_generate_new(&remote, KEY_TYPE, None).await.unwrap();
+
+		assert_eq!(CryptoStore::ecdsa_public_keys(&remote, KEY_TYPE).await, vec![public]);
+		let signature = CryptoStore::sign_with(&remote, KEY_TYPE, &public.into(), b"message")
+			.await
+			.unwrap()
+			.unwrap();
+		let signature = ecdsa::Signature::from_raw(signature.try_into().unwrap());
+		assert!(ecdsa::Pair::verify(&signature, b"message", &public));
+	}
+
+	#[test]
+	fn open_rejects_unsupported_uris() {
+		assert!(RemoteKeystore::open("http://127.0.0.1:9955").is_err());
+		// The signer protocol is not authenticated
+		assert!(RemoteKeystore::open("tcp://10.0.0.1:9955").is_err());
+		assert!(RemoteKeystore::open("tcp://signer.example.com:9955").is_err());
+		assert_eq!(
+			RemoteKeystore::open("tcp://[::1]:9955").unwrap().endpoint,
+			Endpoint::Tcp("[::1]:9955".parse().unwrap())
+		);
+		assert_eq!(
+			RemoteKeystore::open("unix:///run/signer.sock").unwrap().endpoint,
+			Endpoint::Unix("/run/signer.sock".into())
+		);
+	}
+}
@@ -7,12 +7,22 @@
 
 use std::sync::Arc;
 
-use jsonrpsee::RpcModule;
-use node_bridge_runtime::{opaque::Block, AccountId, Balance, Index};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+	RpcModule,
+};
+use node_bridge_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Index};
+use pallet_bridge::helpers::{Challenge, ProcessAt, TxStatus};
+use pallet_bridge_runtime_api::BridgeApi as BridgeRuntimeApi;
+use primitives::bridge::TxHash;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 
 pub use sc_rpc_api::DenyUnsafe;
 
@@ -26,6 +36,136 @@ pub struct FullDeps<C, P> {
 	pub deny_unsafe: DenyUnsafe,
 }
 
+/// Error code of a failed runtime API call.
+const RUNTIME_ERROR: i32 = 1;
+
+/// Bridge RPC methods, to follow transactions without decoding raw storage.
+#[rpc(client, server)]
+pub trait BridgeApi<BlockHash> {
+	/// Status of a transaction, `None` until enough relayers attested to it, kept once settled.
+	#[method(name = "bridge_transactionStatus")]
+	fn transaction_status(
+		&self,
+		transaction_hash: TxHash,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<TxStatus<BlockNumber>>>;
+
+	/// Transactions waiting for their challenge period to end, with when they are processed.
+	#[method(name = "bridge_pendingTransactions")]
+	fn pending_transactions(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(TxHash, ProcessAt<BlockNumber>)>>;
+
+	/// Challenges waiting for a resolution.
+	#[method(name = "bridge_openChallenges")]
+	fn open_challenges(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(TxHash, Challenge<AccountId, BlockNumber, Bytes>)>>;
+
+	/// Whether `relayer` already attested to a transaction.
+	#[method(name = "bridge_attested")]
+	fn attested(
+		&self,
+		transaction_hash: TxHash,
+		relayer: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<bool>;
+
+	/// Relayers allowed to submit transactions.
+	#[method(name = "bridge_relayers")]
+	fn relayers(&self, at: Option<BlockHash>) -> RpcResult<Vec<AccountId>>;
+}
+
+/// Implements the bridge RPC methods with the `BridgeApi` runtime API.
+pub struct Bridge<C> {
+	client: Arc<C>,
+}
+
+impl<C> Bridge<C> {
+	/// Create new `Bridge` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+
+	fn at(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block>
+	where
+		C: HeaderBackend<Block>,
+	{
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+
+fn runtime_error(error: impl ToString, message: &'static str) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(RUNTIME_ERROR, message, Some(error.to_string()))).into()
+}
+
+impl<C> BridgeApiServer<<Block as BlockT>::Hash> for Bridge<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BridgeRuntimeApi<Block, AccountId, BlockNumber>,
+{
+	fn transaction_status(
+		&self,
+		transaction_hash: TxHash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<TxStatus<BlockNumber>>> {
+		self.client
+			.runtime_api()
+			.transaction_status(&self.at(at), transaction_hash)
+			.map_err(|e| runtime_error(e, "Unable to query transaction status."))
+	}
+
+	fn pending_transactions(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(TxHash, ProcessAt<BlockNumber>)>> {
+		self.client
+			.runtime_api()
+			.pending_transactions(&self.at(at))
+			.map_err(|e| runtime_error(e, "Unable to query pending transactions."))
+	}
+
+	fn open_challenges(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(TxHash, Challenge<AccountId, BlockNumber, Bytes>)>> {
+		let challenges = self
+			.client
+			.runtime_api()
+			.open_challenges(&self.at(at))
+			.map_err(|e| runtime_error(e, "Unable to query open challenges."))?;
+		Ok(challenges
+			.into_iter()
+			.map(|(transaction_hash, challenge)| {
+				let Challenge { challenger, bond, reason, evidence, expires_at } = challenge;
+				let evidence = evidence.map(Bytes);
+				(transaction_hash, Challenge { challenger, bond, reason, evidence, expires_at })
+			})
+			.collect())
+	}
+
+	fn attested(
+		&self,
+		transaction_hash: TxHash,
+		relayer: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<bool> {
+		self.client
+			.runtime_api()
+			.attested(&self.at(at), transaction_hash, relayer)
+			.map_err(|e| runtime_error(e, "Unable to query attestations."))
+	}
+
+	fn relayers(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<AccountId>> {
+		self.client
+			.runtime_api()
+			.relayers(&self.at(at))
+			.map_err(|e| runtime_error(e, "Unable to query relayers."))
+	}
+}
+
 /// Instantiate all full RPC extensions.
 pub fn create_full<C, P>(
 	deps: FullDeps<C, P>,
@@ -36,6 +176,7 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: BridgeRuntimeApi<Block, AccountId, BlockNumber>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
@@ -46,12 +187,8 @@ where
 	let FullDeps { client, pool, deny_unsafe } = deps;
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
-
-	// Extend this RPC with a custom API by using the following syntax.
-	// `YourRpcStruct` should have a reference to a client, which is needed
-	// to call into the runtime.
-	// `module.merge(YourRpcTrait::into_rpc(YourRpcStruct::new(ReferenceToClient, ...)))?;`
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(Bridge::new(client).into_rpc())?;
 
 	Ok(module)
 }
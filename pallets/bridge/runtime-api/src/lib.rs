@@ -0,0 +1,28 @@
+//! Runtime API of the bridge pallet, for frontends and tools to follow transactions without
+//! decoding raw storage.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_bridge::helpers::{Challenge, ProcessAt, TxStatus};
+use primitives::bridge::TxHash;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait BridgeApi<AccountId, BlockNumber>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Status of a transaction, `None` until enough relayers attested to it, kept once settled
+		fn transaction_status(transaction_hash: TxHash) -> Option<TxStatus<BlockNumber>>;
+		/// Transactions waiting for their challenge period to end, with when they are processed
+		fn pending_transactions() -> Vec<(TxHash, ProcessAt<BlockNumber>)>;
+		/// Challenges waiting for a resolution
+		fn open_challenges() -> Vec<(TxHash, Challenge<AccountId, BlockNumber, Vec<u8>>)>;
+		/// Whether `relayer` already attested to a transaction
+		fn attested(transaction_hash: TxHash, relayer: AccountId) -> bool;
+		/// Relayers allowed to submit transactions
+		fn relayers() -> Vec<AccountId>;
+	}
+}
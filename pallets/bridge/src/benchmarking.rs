@@ -4,16 +4,190 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as Bridge;
-use frame_benchmarking::{account, benchmarks};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_system::RawOrigin;
+use sp_core::H160;
 
 const SEED: u32 = 0;
 const MAX_MEMBERS: u32 = 100;
+const RATE_LIMIT: Balance = Balance::MAX / 2;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, T::RelayerMinStake::get().saturating_mul(10));
+	who
+}
+
+fn payment(index: u32) -> TxData {
+	TxData::Payment {
+		amount: 1_000_000,
+		address: H160::from_low_u64_be(index as u64 + 1),
+		currency: ExternalCurrency::default(),
+	}
+}
+
+/// Schedule a transaction as if `RelayerThreshold` relayers attested to it
+fn add_transaction<T: Config>(index: u32) -> TxHash {
+	CurrencyRegistry::<T>::insert(ExternalCurrency::default(), T::AssetId::get());
+	let relayer: T::AccountId = account("relayer", 0, SEED);
+	let transaction_hash = TxHash::from_low_u64_be(index as u64);
+	Bridge::<T>::add_to_relay(relayer, index as u64, transaction_hash, payment(index), 0).unwrap();
+	transaction_hash
+}
+
+/// Store the matching attestations of `r` bonded relayers to a scheduled transaction
+fn add_attestations<T: Config>(transaction_hash: TxHash, r: u32) -> DispatchResult {
+	let attestation = ProcessTransactionDetails::<T>::get(transaction_hash)
+		.map(|(ledger_index, transaction, _)| (ledger_index, transaction))
+		.ok_or(Error::<T>::UnknownTransaction)?;
+	for i in 0..r {
+		let relayer = funded_account::<T>("relayer", i);
+		if !RelayerBonds::<T>::contains_key(relayer) {
+			Bridge::<T>::bond(&relayer, T::RelayerMinStake::get())?;
+		}
+		TransactionAttestations::<T>::insert(transaction_hash, relayer, attestation.clone());
+	}
+	Ok(())
+}
+
+fn challenge<T: Config>(transaction_hash: TxHash) -> T::AccountId {
+	let challenger = funded_account::<T>("challenger", 0);
+	Bridge::<T>::submit_challenge(
+		RawOrigin::Signed(challenger).into(),
+		transaction_hash,
+		ChallengeReason::Other,
+		None,
+	)
+	.unwrap();
+	challenger
+}
+
+/// Apply limits to every transaction without deferring any, to account for the window updates
+fn rate_limit_all<T: Config>() {
+	let limit = RateLimit { amount: RATE_LIMIT, period: 1 };
+	AssetRateLimits::<T>::insert(T::AssetId::get(), limit);
+	DestinationRateLimit::<T>::put(limit);
+}
+
+/// Charge a fee on every transaction, shared between the relayer and the treasury
+fn charge_fees<T: Config>() {
+	BridgeFeeSchedule::<T>::put(FeeSchedule {
+		flat: 1_000,
+		basis_points: 100,
+		relayer_share: Perbill::from_percent(50),
+	});
+	MinimumFees::<T>::insert(T::AssetId::get(), 1_000);
+}
+
+/// Take a transaction off the schedule of block `block_number`
+fn unschedule<T: Config>(transaction_hash: TxHash, block_number: T::BlockNumber) {
+	ProcessTransaction::<T>::mutate(block_number, |tx_hashes| {
+		if let Some(tx_hashes) = tx_hashes {
+			tx_hashes.retain(|tx_hash| *tx_hash != transaction_hash);
+		}
+	});
+}
+
+/// Move a transaction scheduled at block `block_number` to the carry-over queue
+fn move_to_carry_over<T: Config>(transaction_hash: TxHash, block_number: T::BlockNumber) {
+	unschedule::<T>(transaction_hash, block_number);
+	Bridge::<T>::carry_over(transaction_hash);
+}
+
+/// Move a transaction scheduled at block `block_number` to the wall clock schedule
+fn move_to_time<T: Config>(
+	transaction_hash: TxHash,
+	block_number: T::BlockNumber,
+	process_time: u64,
+) -> DispatchResult {
+	unschedule::<T>(transaction_hash, block_number);
+	TimedProcessTransaction::<T>::try_append(process_time, transaction_hash)
+		.map_err(|_| Error::<T>::TooManyTransactions)?;
+	TimedProcessCursor::<T>::put(process_time);
+	TransactionStatus::<T>::insert(
+		transaction_hash,
+		TxStatus::Pending(ProcessAt::Time(process_time)),
+	);
+	Ok(())
+}
+
+/// Use up the destination window of `payment(index)` at block `block_number`, so it is deferred
+fn exhaust_window<T: Config>(index: u32, block_number: T::BlockNumber) {
+	let address = H160::from_low_u64_be(index as u64 + 1);
+	DestinationMintWindows::<T>::insert(
+		address,
+		MintWindow { start: block_number, minted: RATE_LIMIT },
+	);
+}
+
+/// Schedule unrelated transactions at block `block_number`, leaving room for `room` more
+fn fill_block<T: Config>(block_number: T::BlockNumber, room: u32) {
+	let tx_hashes: Vec<TxHash> = (0..T::MaxTransactionsPerBlock::get().saturating_sub(room))
+		.map(|i| TxHash::from_low_u64_be(u64::MAX - i as u64))
+		.collect();
+	ProcessTransaction::<T>::insert(block_number, TxHashesOf::<T>::try_from(tx_hashes).unwrap());
+}
+
+fn process_block<T: Config>() -> T::BlockNumber {
+	frame_system::Pallet::<T>::block_number() + T::ChallengePeriod::get().into()
+}
 
 benchmarks! {
+	submit_transaction {
+		let r in 1 .. T::MaxRelayers::get();
+		CurrencyRegistry::<T>::insert(ExternalCurrency::default(), T::AssetId::get());
+		let transaction_hash = TxHash::from_low_u64_be(1);
+		let timestamp = Bridge::<T>::unix_now();
+		// The other active relayers already attested to the same data, the call completes the
+		// quorum
+		let attestation =
+			(1, Transaction { transaction_hash, transaction: payment(1), timestamp });
+		for i in 0 .. r {
+			let relayer = funded_account::<T>("relayer", i);
+			Bridge::<T>::bond(&relayer, T::RelayerMinStake::get())?;
+			Bridge::<T>::initialize_relayer(&vec![relayer]);
+			if i > 0 {
+				TransactionAttestations::<T>::insert(transaction_hash, relayer, attestation.clone());
+			}
+		}
+		RelayerThreshold::<T>::put(r);
+		let relayer = funded_account::<T>("relayer", 0);
+	}: _(RawOrigin::Signed(relayer), 1, transaction_hash, payment(1), timestamp)
+	verify {
+		assert!(ProcessTransactionDetails::<T>::contains_key(transaction_hash));
+	}
+
+	submit_challenge {
+		let transaction_hash = add_transaction::<T>(1);
+		let challenger = funded_account::<T>("challenger", 0);
+		let evidence = vec![0u8; T::MaxEvidenceLength::get() as usize];
+	}: _(RawOrigin::Signed(challenger), transaction_hash, ChallengeReason::Other, Some(evidence))
+	verify {
+		assert!(ChallengeTransactionList::<T>::contains_key(transaction_hash));
+	}
+
+	failed_challenge {
+		let transaction_hash = add_transaction::<T>(1);
+		challenge::<T>(transaction_hash);
+	}: _(RawOrigin::Root, transaction_hash)
+	verify {
+		assert!(!ChallengeTransactionList::<T>::contains_key(transaction_hash));
+	}
+
+	success_challenge {
+		let r in 1 .. T::MaxRelayers::get();
+		let transaction_hash = add_transaction::<T>(1);
+		add_attestations::<T>(transaction_hash, r)?;
+		challenge::<T>(transaction_hash);
+	}: _(RawOrigin::Root, transaction_hash)
+	verify {
+		assert_eq!(TransactionStatus::<T>::get(transaction_hash), Some(TxStatus::Rejected));
+	}
+
 	add_relayer {
 		let m in 1 .. MAX_MEMBERS;
 		let member: T::AccountId = account("some_account", m, SEED);
+		T::Currency::make_free_balance_be(&member, T::RelayerMinStake::get().saturating_mul(2));
 	}: add_relayer(RawOrigin::Root, member)
 	verify {
 		assert!(Relayer::<T>::contains_key(member));
@@ -22,11 +196,261 @@ benchmarks! {
 	remove_relayer {
 		let m in 1 .. MAX_MEMBERS;
 		let member: T::AccountId = account("some_account", m, SEED);
-		<Relayer<T>>::insert(member, true);
+		Bridge::<T>::initialize_relayer(&vec![member]);
 	}: remove_relayer(RawOrigin::Root, member)
 	verify {
 		assert!(!Relayer::<T>::contains_key(member));
 	}
 
+	register_relayer {
+		let relayer = funded_account::<T>("relayer", 0);
+		RelayerThreshold::<T>::put(2);
+	}: _(RawOrigin::Signed(relayer), T::RelayerMinStake::get())
+	verify {
+		assert!(Relayer::<T>::contains_key(relayer));
+	}
+
+	unbond_relayer {
+		let relayer = funded_account::<T>("relayer", 0);
+		RelayerThreshold::<T>::put(2);
+		Bridge::<T>::register_relayer(RawOrigin::Signed(relayer).into(), T::RelayerMinStake::get())?;
+	}: _(RawOrigin::Signed(relayer), T::RelayerMinStake::get())
+	verify {
+		assert_eq!(Relayer::<T>::get(relayer), Some(false));
+	}
+
+	withdraw_unbonded {
+		let relayer = funded_account::<T>("relayer", 0);
+		RelayerThreshold::<T>::put(2);
+		Bridge::<T>::register_relayer(RawOrigin::Signed(relayer).into(), T::RelayerMinStake::get())?;
+		Bridge::<T>::unbond_relayer(RawOrigin::Signed(relayer).into(), T::RelayerMinStake::get())?;
+		frame_system::Pallet::<T>::set_block_number(
+			frame_system::Pallet::<T>::block_number() + T::RelayerUnbondingPeriod::get().into(),
+		);
+	}: _(RawOrigin::Signed(relayer))
+	verify {
+		assert!(!RelayerBonds::<T>::contains_key(relayer));
+	}
+
+	set_relayer_threshold {
+	}: _(RawOrigin::Root, 2)
+	verify {
+		assert_eq!(RelayerThreshold::<T>::get(), 2);
+	}
+
+	set_challenge_period_tiers {
+		let n in 0 .. T::MaxChallengePeriodTiers::get();
+		let tiers: Vec<(Balance, u32)> = (0 .. n).map(|i| (i as Balance + 1, i + 1)).collect();
+	}: _(RawOrigin::Root, tiers)
+	verify {
+		assert_eq!(ChallengePeriodTiers::<T>::get().len() as u32, n);
+	}
+
+	set_asset_rate_limit {
+		let limit = RateLimit { amount: 1_000_000, period: 100 };
+	}: _(RawOrigin::Root, T::AssetId::get(), Some(limit))
+	verify {
+		assert_eq!(AssetRateLimits::<T>::get(T::AssetId::get()), Some(limit));
+	}
+
+	set_destination_rate_limit {
+		let limit = RateLimit { amount: 1_000_000, period: 100 };
+	}: _(RawOrigin::Root, Some(limit))
+	verify {
+		assert_eq!(DestinationRateLimit::<T>::get(), Some(limit));
+	}
+
+	set_bridge_mode {
+		let mode = BridgeMode {
+			inbound_submission_paused: true,
+			inbound_processing_paused: true,
+			challenges_paused: true,
+			outbound_paused: true,
+		};
+	}: _(RawOrigin::Root, mode)
+	verify {
+		assert_eq!(CurrentBridgeMode::<T>::get(), mode);
+	}
+
+	set_fee_schedule {
+		let schedule = FeeSchedule {
+			flat: 1_000,
+			basis_points: 100,
+			relayer_share: Perbill::from_percent(50),
+		};
+	}: _(RawOrigin::Root, schedule)
+	verify {
+		assert_eq!(BridgeFeeSchedule::<T>::get(), schedule);
+	}
+
+	set_minimum_fee {
+	}: _(RawOrigin::Root, T::AssetId::get(), Some(1_000))
+	verify {
+		assert_eq!(MinimumFees::<T>::get(T::AssetId::get()), Some(1_000));
+	}
+
+	register_currency {
+		let currency = ExternalCurrency::default();
+	}: _(RawOrigin::Root, currency, T::AssetId::get())
+	verify {
+		assert!(CurrencyRegistry::<T>::contains_key(currency));
+	}
+
+	deregister_currency {
+		let currency = ExternalCurrency::default();
+		CurrencyRegistry::<T>::insert(currency, T::AssetId::get());
+	}: _(RawOrigin::Root, currency)
+	verify {
+		assert!(!CurrencyRegistry::<T>::contains_key(currency));
+	}
+
+	retry_failed {
+		let transaction_hash = add_transaction::<T>(1);
+		FailedTransactions::<T>::insert(transaction_hash, DispatchError::Other("benchmark"));
+		TransactionStatus::<T>::insert(transaction_hash, TxStatus::Failed);
+		rate_limit_all::<T>();
+		charge_fees::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), transaction_hash)
+	verify {
+		assert_eq!(TransactionStatus::<T>::get(transaction_hash), Some(TxStatus::Settled));
+	}
+
+	admit_transaction {
+		let transaction_hash = TxHash::from_low_u64_be(1);
+	}: _(RawOrigin::Root, transaction_hash)
+	verify {
+		assert!(AdmittedTransactions::<T>::contains_key(transaction_hash));
+	}
+
+	withdraw {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Assets::mint_into(T::AssetId::get(), &caller, 2_000_000)?;
+	}: _(RawOrigin::Signed(caller), 1_000_000, H160::from_low_u64_be(1))
+	verify {
+		assert!(WithdrawalRequests::<T>::contains_key(0));
+	}
+
+	execute_withdrawal {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Assets::mint_into(T::AssetId::get(), &caller, 2_000_000)?;
+		Bridge::<T>::withdraw(
+			RawOrigin::Signed(caller).into(),
+			1_000_000,
+			H160::from_low_u64_be(1),
+		)?;
+		let relayer: T::AccountId = account("relayer", 0, SEED);
+		Bridge::<T>::initialize_relayer(&vec![relayer]);
+	}: _(RawOrigin::Signed(relayer), 0, 1, TxHash::from_low_u64_be(1))
+	verify {
+		assert_ne!(
+			WithdrawalRequests::<T>::get(0).map(|request| request.status),
+			Some(WithdrawalStatus::Requested)
+		);
+	}
+
+	report_reserve {
+		let r in 0 .. T::MaxRelayers::get() - 1;
+		let currency = ExternalCurrency::default();
+		CurrencyRegistry::<T>::insert(currency, T::AssetId::get());
+		// The other relayers already reported, the call completes the quorum
+		for i in 0 ..= r {
+			let relayer = funded_account::<T>("relayer", i);
+			Bridge::<T>::bond(&relayer, T::RelayerMinStake::get())?;
+			Bridge::<T>::initialize_relayer(&vec![relayer]);
+			if i > 0 {
+				ReserveAttestations::<T>::insert(currency, relayer, (1, 0));
+			}
+		}
+		RelayerThreshold::<T>::put(r + 1);
+		let relayer = funded_account::<T>("relayer", 0);
+		T::Assets::mint_into(T::AssetId::get(), &relayer, 2_000_000)?;
+	}: _(RawOrigin::Signed(relayer), currency, 1, 0)
+	verify {
+		assert!(CurrentBridgeMode::<T>::get().inbound_processing_paused);
+	}
+
+	process_tx {
+		let n in 0 .. T::MaxProcessPerBlock::get();
+		let r in 1 .. T::MaxRelayers::get();
+		let block_number = process_block::<T>();
+		let tx_hashes: Vec<TxHash> = (0 .. n).map(add_transaction::<T>).collect();
+		for transaction_hash in &tx_hashes {
+			add_attestations::<T>(*transaction_hash, r)?;
+		}
+		rate_limit_all::<T>();
+		charge_fees::<T>();
+		// Take every path of a matured transaction: carried over from an earlier block, due by
+		// wall clock, deferred by a rate limit to a nearly full block, or minted at its block
+		let now = Bridge::<T>::unix_now();
+		let deferred_to = block_number + 1u32.into();
+		let mut deferred = Vec::new();
+		for (index, transaction_hash) in tx_hashes.iter().enumerate() {
+			match index % 4 {
+				0 => move_to_carry_over::<T>(*transaction_hash, block_number),
+				1 => move_to_time::<T>(*transaction_hash, block_number, now)?,
+				2 => {
+					exhaust_window::<T>(index as u32, block_number);
+					deferred.push(*transaction_hash);
+				},
+				_ => {},
+			}
+		}
+		fill_block::<T>(deferred_to, deferred.len() as u32);
+		frame_system::Pallet::<T>::set_block_number(block_number);
+	}: {
+		Bridge::<T>::process_tx(block_number);
+	}
+	verify {
+		for transaction_hash in tx_hashes {
+			let status = if deferred.contains(&transaction_hash) {
+				TxStatus::Deferred(deferred_to)
+			} else {
+				TxStatus::Settled
+			};
+			assert_eq!(TransactionStatus::<T>::get(transaction_hash), Some(status));
+		}
+	}
+
+	clear_storages {
+		let n in 0 .. T::MaxProcessPerBlock::get();
+		let r in 1 .. T::MaxRelayers::get();
+		let block_number = process_block::<T>();
+		let tx_hashes: Vec<TxHash> = (0 .. n).map(add_transaction::<T>).collect();
+		for transaction_hash in &tx_hashes {
+			add_attestations::<T>(*transaction_hash, r)?;
+		}
+		frame_system::Pallet::<T>::set_block_number(block_number);
+		Bridge::<T>::process_tx(block_number);
+		let clear_block_number = block_number + T::ClearTxPeriod::get().into();
+	}: {
+		Bridge::<T>::clear_storages(clear_block_number);
+	}
+	verify {
+		for transaction_hash in tx_hashes {
+			assert!(!ProcessTransactionDetails::<T>::contains_key(transaction_hash));
+			assert_eq!(TransactionAttestations::<T>::iter_prefix(transaction_hash).count(), 0);
+		}
+	}
+
+	expire_challenges {
+		let n in 0 .. T::MaxTransactionsPerBlock::get();
+		let r in 1 .. T::MaxRelayers::get();
+		let tx_hashes: Vec<TxHash> = (0 .. n).map(add_transaction::<T>).collect();
+		for transaction_hash in &tx_hashes {
+			add_attestations::<T>(*transaction_hash, r)?;
+			challenge::<T>(*transaction_hash);
+		}
+		let block_number = frame_system::Pallet::<T>::block_number() +
+			T::ChallengeResolutionPeriod::get().into();
+	}: {
+		Bridge::<T>::expire_challenges(block_number);
+	}
+	verify {
+		for transaction_hash in tx_hashes {
+			assert!(!ChallengeTransactionList::<T>::contains_key(transaction_hash));
+		}
+	}
+
 	impl_benchmark_test_suite!(Bridge, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -0,0 +1,33 @@
+//! Relayer keys of the offchain worker, kept in the node keystore under `KEY_TYPE`
+
+use frame_system::offchain::AppCrypto;
+use primitives::signature::{signing_digest, BridgeSignature, BridgeSigner};
+use sp_core::ecdsa;
+use sp_runtime::{
+	traits::{IdentifyAccount, Verify},
+	RuntimeAppPublic,
+};
+
+pub use primitives::crypto::{Public, KEY_TYPE};
+
+/// Signs the `signing_digest` of payloads with the keystore key of a `BridgeSigner` address
+pub struct RelayerAuthId;
+
+impl AppCrypto<BridgeSigner, BridgeSignature> for RelayerAuthId {
+	type RuntimeAppPublic = Public;
+	type GenericPublic = ecdsa::Public;
+	type GenericSignature = ecdsa::Signature;
+
+	fn sign(payload: &[u8], public: BridgeSigner) -> Option<BridgeSignature> {
+		let key: ecdsa::Public = Public::all()
+			.into_iter()
+			.find(|key| BridgeSigner::from(key.clone()) == public)?
+			.into();
+		sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &key, &signing_digest(payload))
+			.map(Into::into)
+	}
+
+	fn verify(payload: &[u8], public: BridgeSigner, signature: BridgeSignature) -> bool {
+		signature.verify(payload, &public.into_account())
+	}
+}
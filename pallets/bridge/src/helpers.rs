@@ -1,9 +1,15 @@
 use codec::{Decode, Encode};
 use frame_support::pallet_prelude::*;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_core::H160;
+use sp_runtime::Perbill;
 
-use primitives::{bridge::TxHash, types::Balance};
+use primitives::{
+	bridge::{Address, CurrencyCode, LedgerIndex, TxHash},
+	types::{Balance, TokenId},
+};
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
 #[scale_info(skip_type_params(T))]
@@ -16,11 +22,184 @@ pub struct Transaction {
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[scale_info(skip_type_params(T))]
 pub enum TxData {
-	Payment { amount: Balance, address: H160 },
+	Payment { amount: Balance, address: H160, currency: ExternalCurrency },
 }
 
 impl Default for TxData {
 	fn default() -> Self {
-		TxData::Payment { amount: 0, address: H160::default() }
+		TxData::Payment {
+			amount: 0,
+			address: H160::default(),
+			currency: ExternalCurrency::default(),
+		}
+	}
+}
+
+/// When a transaction is due to be processed once its challenge period ends
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ProcessAt<BlockNumber> {
+	/// At the given block
+	Block(BlockNumber),
+	/// At the first block from the given Unix time in seconds
+	Time(u64),
+}
+
+/// Lifecycle of a transaction once enough relayers attested to it
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TxStatus<BlockNumber> {
+	/// Waiting for the challenge period to end
+	Pending(ProcessAt<BlockNumber>),
+	/// Challenged before being processed, waiting for a resolution
+	Challenged(ProcessAt<BlockNumber>),
+	/// Proven invalid by a challenge, the same hash may be submitted again
+	Rejected,
+	/// Minted to the destination
+	Settled,
+	/// Minting failed, waiting for a retry
+	Failed,
+	/// Past its challenge period, waiting for room in a rate limit window at the given block
+	/// It can no longer be challenged
+	Deferred(BlockNumber),
+}
+
+/// Why a challenger disputes a transaction
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ChallengeReason {
+	/// No such transaction on the external ledger
+	NotOnLedger,
+	AmountMismatch,
+	DestinationMismatch,
+	CurrencyMismatch,
+	Other,
+}
+
+/// Challenge of a transaction, with the bond reserved from the challenger
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Challenge<AccountId, BlockNumber, Evidence> {
+	pub challenger: AccountId,
+	pub bond: Balance,
+	pub reason: ChallengeReason,
+	/// Data supporting the challenge, for the resolver to review
+	pub evidence: Option<Evidence>,
+	/// Block at which the challenge is resolved by `ChallengeExpiryPolicy` if still open
+	pub expires_at: BlockNumber,
+}
+
+/// How a challenge left unresolved for `ChallengeResolutionPeriod` is resolved
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ChallengeExpiry {
+	/// The transaction is rejected
+	Reject,
+	/// The transaction is released to be processed
+	Release,
+}
+
+/// Fee deducted from inbound payments, a flat amount plus basis points of the payment
+#[derive(
+	PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+)]
+pub struct FeeSchedule {
+	pub flat: Balance,
+	pub basis_points: u32,
+	/// Part of the fee paid to the relayer that submitted the payment, the rest goes to the
+	/// treasury
+	pub relayer_share: Perbill,
+}
+
+/// Maximum amount minted within a window of `period` blocks
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RateLimit {
+	pub amount: Balance,
+	pub period: u32,
+}
+
+/// Amount minted since the start of a rate limit window
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MintWindow<BlockNumber> {
+	pub start: BlockNumber,
+	pub minted: Balance,
+}
+
+/// Rate limit reached by a transfer
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RateLimitScope {
+	/// Limit on the amount of an asset minted
+	Asset(TokenId),
+	/// Limit on the amount minted to a destination address
+	Destination(Address),
+}
+
+/// Parts of the bridge paused during an incident, everything runs by default
+#[derive(
+	PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+)]
+pub struct BridgeMode {
+	/// Relayers cannot submit inbound transactions
+	pub inbound_submission_paused: bool,
+	/// Matured inbound transactions keep their place in the queue instead of being minted
+	pub inbound_processing_paused: bool,
+	/// Transactions cannot be challenged
+	pub challenges_paused: bool,
+	/// Withdrawals cannot be requested
+	pub outbound_paused: bool,
+}
+
+/// Balance of the door account on the external ledger reported by the relayer quorum, with the
+/// supply minted against it when reported
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ReserveReport<AccountId, BlockNumber> {
+	/// Relayer whose report completed the quorum
+	pub relayer: AccountId,
+	/// Ledger index the balance was read at
+	pub ledger_index: LedgerIndex,
+	pub reserve: Balance,
+	pub supply: Balance,
+	pub reported_at: BlockNumber,
+}
+
+/// Currency on the external ledger, identified by its code and issuer address
+#[derive(
+	PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ExternalCurrency {
+	pub code: CurrencyCode,
+	pub issuer: Address,
+}
+
+/// Outbound request to pay out burned tokens on the external ledger
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct WithdrawalRequest<AccountId> {
+	pub who: AccountId,
+	pub amount: Balance,
+	pub destination: Address,
+	pub status: WithdrawalStatus,
+}
+
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum WithdrawalStatus {
+	/// Tokens burned, waiting for a relayer to pay out on the external ledger
+	Requested,
+	/// Paid out by the external ledger transaction
+	Executed { ledger_index: LedgerIndex, transaction_hash: TxHash },
+}
+
+/// Stake reserved by a relayer, slashable while bonded or unbonding
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+pub struct RelayerBond<BlockNumber> {
+	/// Stake backing the relayer
+	pub active: Balance,
+	/// Stake leaving the relayer, withdrawable from `unlock_at`
+	pub unbonding: Balance,
+	pub unlock_at: BlockNumber,
+}
+
+impl<BlockNumber> RelayerBond<BlockNumber> {
+	pub fn total(&self) -> Balance {
+		self.active.saturating_add(self.unbonding)
 	}
 }
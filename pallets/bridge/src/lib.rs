@@ -5,44 +5,89 @@
 /// <https://docs.substrate.io/reference/frame-pallets/>
 pub use pallet::*;
 
-use crate::helpers::{Transaction, TxData};
+use crate::helpers::{
+	BridgeMode, Challenge, ChallengeExpiry, ChallengeReason, ExternalCurrency, FeeSchedule,
+	MintWindow, ProcessAt, RateLimit, RateLimitScope, RelayerBond, ReserveReport, Transaction,
+	TxData, TxStatus, WithdrawalRequest, WithdrawalStatus,
+};
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
 		fungibles::{Inspect, Mutate, Transfer},
-		UnixTime,
+		BalanceStatus, Currency, ExistenceRequirement, ReservableCurrency, UnixTime,
 	},
-	weights::constants::RocksDbWeight as DbWeight,
+	PalletId,
+};
+use frame_system::{
+	offchain::{AppCrypto, CreateSignedTransaction},
+	pallet_prelude::*,
 };
-use frame_system::pallet_prelude::*;
 use primitives::{
-	bridge::{LedgerIndex, TxHash},
+	bridge::{Address, LedgerIndex, TxHash, WithdrawalId},
 	types::{AccountId, Balance, Timestamp, TokenId},
 };
+use sp_runtime::{traits::AccountIdConversion, Perbill, Permill};
 use sp_std::{prelude::*, vec};
 
+/// Number of blocks searched for room when a deferred or settled transaction is rescheduled
+pub const MAX_RESCHEDULE_BLOCKS: u32 = 10;
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
-mod helpers;
+pub mod crypto;
+pub mod helpers;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
+pub mod offchain;
 #[cfg(test)]
 mod tests;
 #[cfg(test)]
+mod tests_bridge_mode;
+#[cfg(test)]
+mod tests_challenge;
+#[cfg(test)]
+mod tests_fee;
+#[cfg(test)]
+mod tests_migration;
+#[cfg(test)]
+mod tests_offchain;
+#[cfg(test)]
+mod tests_rate_limit;
+#[cfg(test)]
 mod tests_relayer;
+#[cfg(test)]
+mod tests_withdrawal;
+pub mod weights;
+pub use weights::WeightInfo;
+
+/// Transactions scheduled at the same block
+pub type TxHashesOf<T> = BoundedVec<TxHash, <T as Config>::MaxTransactionsPerBlock>;
+
+/// Challenge stored for a transaction, with its evidence bounded by `MaxEvidenceLength`
+pub type ChallengeOf<T> = Challenge<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::BlockNumber,
+	BoundedVec<u8, <T as Config>::MaxEvidenceLength>,
+>;
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 
+	/// The current storage version
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config<AccountId = AccountId> {
+	pub trait Config:
+		frame_system::Config<AccountId = AccountId> + CreateSignedTransaction<Call<Self>>
+	{
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
@@ -53,6 +98,9 @@ pub mod pallet {
 		/// Allowed origins to add/remove the relayers
 		type ApproveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// Allowed origins to pause and resume parts of the bridge
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
 		///  Asset Id set at runtime
 		#[pallet::constant]
 		type AssetId: Get<TokenId>;
@@ -65,8 +113,109 @@ pub mod pallet {
 		#[pallet::constant]
 		type ClearTxPeriod: Get<u32>;
 
+		/// Maximum number of matured transactions processed in `on_initialize`
+		/// The rest is carried over to later blocks
+		#[pallet::constant]
+		type MaxProcessPerBlock: Get<u32>;
+
+		/// Maximum number of transactions scheduled to be processed, cleared or have their
+		/// challenge expire at the same block
+		#[pallet::constant]
+		type MaxTransactionsPerBlock: Get<u32>;
+
 		/// Unix time
 		type UnixTime: UnixTime;
+
+		/// Maximum number of seconds a submitted transaction timestamp may be ahead of `UnixTime`
+		#[pallet::constant]
+		type MaxTimestampDrift: Get<u64>;
+
+		/// Maximum age in seconds of a submitted transaction timestamp
+		#[pallet::constant]
+		type MaxTransactionAge: Get<u64>;
+
+		/// Maximum number of amount thresholds in `ChallengePeriodTiers`
+		#[pallet::constant]
+		type MaxChallengePeriodTiers: Get<u32>;
+
+		/// Challenge period in seconds of `UnixTime`, used instead of `ChallengePeriod` when set
+		/// Each block scans at most `MaxTimedScanPerBlock` seconds of it
+		#[pallet::constant]
+		type WallClockChallengePeriod: Get<Option<u64>>;
+
+		/// Maximum number of seconds of `UnixTime` scanned for matured transactions per block when
+		/// `WallClockChallengePeriod` is set, which must cover the block time
+		#[pallet::constant]
+		type MaxTimedScanPerBlock: Get<u32>;
+
+		/// Currency used to bond challenges
+		type Currency: ReservableCurrency<Self::AccountId, Balance = Balance>;
+
+		/// Bond reserved from a challenger until the challenge is resolved
+		#[pallet::constant]
+		type ChallengeBond: Get<Balance>;
+
+		/// Reward paid from the bridge account when a challenge succeeds
+		#[pallet::constant]
+		type ChallengeReward: Get<Balance>;
+
+		/// Maximum length of the evidence attached to a challenge
+		#[pallet::constant]
+		type MaxEvidenceLength: Get<u32>;
+
+		/// Number of blocks `ApproveOrigin` has to resolve a challenge before it expires
+		#[pallet::constant]
+		type ChallengeResolutionPeriod: Get<u32>;
+
+		/// Weight information for extrinsics and block processing in this pallet
+		type WeightInfo: WeightInfo;
+
+		/// Resolution applied to expired challenges
+		/// The challenger bond is returned and no relayer is slashed either way
+		#[pallet::constant]
+		type ChallengeExpiryPolicy: Get<ChallengeExpiry>;
+
+		/// Id of the bridge account funding challenge rewards
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Maximum number of relayer attestations kept for a transaction, bounding the weight of
+		/// counting and slashing them
+		#[pallet::constant]
+		type MaxRelayers: Get<u32>;
+
+		/// Minimum stake a relayer must keep bonded to stay active
+		#[pallet::constant]
+		type RelayerMinStake: Get<Balance>;
+
+		/// Number of blocks unbonded relayer stake stays slashable before it can be withdrawn
+		#[pallet::constant]
+		type RelayerUnbondingPeriod: Get<u32>;
+
+		/// Part of a relayer stake slashed when a transaction it attested is successfully
+		/// challenged
+		#[pallet::constant]
+		type RelayerSlash: Get<Perbill>;
+
+		/// Part of the supply of a bridged asset its reported reserve may fall short of before
+		/// inbound processing is paused
+		#[pallet::constant]
+		type ReserveTolerance: Get<Perbill>;
+
+		/// Account receiving the part of the bridge fees not paid to relayers
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// Relayer keys the offchain worker signs `submit_transaction` with
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// Maximum number of external ledgers the offchain worker scans per block
+		#[pallet::constant]
+		type OffchainLedgersPerRun: Get<u32>;
+
+		/// Maximum number of pending transactions the offchain worker verifies per block
+		#[pallet::constant]
+		type OffchainTransactionsPerRun: Get<u32>;
 	}
 
 	#[pallet::storage]
@@ -74,11 +223,90 @@ pub mod pallet {
 	/// List of all  transaction relayers
 	pub type Relayer<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, bool>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn relayer_bond)]
+	/// Stake bonded by each relayer
+	pub type RelayerBonds<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RelayerBond<T::BlockNumber>>;
+
+	#[pallet::storage]
+	/// Relayers removed by `ApproveOrigin`, they can only come back through `add_relayer`
+	pub type RemovedRelayers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	#[pallet::storage]
+	/// Transactions `ApproveOrigin` admitted past `MaxTransactionAge`, for deposits delayed by a
+	/// pause or an outage of the bridge, until they are scheduled
+	pub type AdmittedTransactions<T: Config> = StorageMap<_, Identity, TxHash, ()>;
+
+	#[pallet::type_value]
+	pub fn DefaultRelayerThreshold() -> u32 {
+		1
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn relayer_count)]
+	/// Number of accounts in `Relayer`, at most `MaxRelayers`
+	pub type RelayerCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn relayer_threshold)]
+	/// Number of matching relayer attestations required before a transaction is scheduled
+	/// Accounts can only register themselves as relayers while it is greater than 1
+	pub type RelayerThreshold<T: Config> =
+		StorageValue<_, u32, ValueQuery, DefaultRelayerThreshold>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn transaction_attestations)]
+	/// Attestations submitted by each relayer for a transaction hash, including the ones that
+	/// disagree with the majority, kept for review until the transaction is cleared
+	/// Attestations of relayers removed or deactivated since are dropped by the next attestation
+	pub type TransactionAttestations<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		TxHash,
+		Blake2_128Concat,
+		T::AccountId,
+		(LedgerIndex, Transaction),
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn challenge_period_tiers)]
+	/// Amount thresholds in ascending order, each mapped to the challenge period of the payments
+	/// reaching it, in blocks or in seconds when `WallClockChallengePeriod` is set
+	/// Payments below the first threshold wait the default challenge period
+	pub type ChallengePeriodTiers<T: Config> =
+		StorageValue<_, BoundedVec<(Balance, u32), T::MaxChallengePeriodTiers>, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn process_transaction)]
 	/// Temporary storage to set the transactions ready to be processed at specified block number
 	pub type ProcessTransaction<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::BlockNumber, Vec<TxHash>, OptionQuery>;
+		StorageMap<_, Blake2_128Concat, T::BlockNumber, TxHashesOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn timed_process_transaction)]
+	/// Transactions ready to be processed from the specified Unix time in seconds, when the
+	/// challenge period is measured by `WallClockChallengePeriod`
+	pub type TimedProcessTransaction<T: Config> =
+		StorageMap<_, Twox64Concat, u64, TxHashesOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn timed_process_cursor)]
+	/// Next second of `TimedProcessTransaction` to be processed, set when the first transaction is
+	/// scheduled there
+	pub type TimedProcessCursor<T: Config> = StorageValue<_, u64, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn carry_over_queue)]
+	/// Matured transactions left over by `MaxProcessPerBlock`, processed by later blocks in FIFO
+	/// order
+	pub type CarryOverQueue<T: Config> = StorageMap<_, Twox64Concat, u64, TxHash>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn carry_over_cursor)]
+	/// Index of the first and the next free entries of `CarryOverQueue` (head, tail)
+	/// Their difference is the depth of the queue
+	pub type CarryOverCursor<T: Config> = StorageValue<_, (u64, u64), ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn process_transaction_details)]
@@ -91,22 +319,188 @@ pub mod pallet {
 	#[pallet::getter(fn settled_transaction_details)]
 	/// Settled transactions stored as history for a specific period
 	pub type SettledTransactionDetails<T: Config> =
-		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<TxHash>>;
+		StorageMap<_, Twox64Concat, T::BlockNumber, TxHashesOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn transaction_status)]
+	/// Status of every transaction accepted by the relayers
+	/// Kept permanently for replay protection after the transaction details are pruned
+	pub type TransactionStatus<T: Config> =
+		StorageMap<_, Identity, TxHash, TxStatus<T::BlockNumber>>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn challenge_transaction_list)]
 	/// Challenge received for a transaction mapped by hash, will be cleared when sudo validates it
-	pub type ChallengeTransactionList<T: Config> = StorageMap<_, Identity, TxHash, T::AccountId>;
+	/// Stores the challenger, the bond reserved from it and the reason of the challenge
+	pub type ChallengeTransactionList<T: Config> = StorageMap<_, Identity, TxHash, ChallengeOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn failed_transactions)]
+	/// Transactions whose mint failed when processed, with the reason of the failure
+	/// Their details are kept until the mint is retried successfully
+	pub type FailedTransactions<T: Config> = StorageMap<_, Identity, TxHash, DispatchError>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn challenge_expiries)]
+	/// Challenged transactions mapped by the block their challenge expires at
+	pub type ChallengeExpiries<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, TxHashesOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn currency_registry)]
+	/// Assets minted for each currency bridged from the external ledger
+	pub type CurrencyRegistry<T: Config> =
+		StorageMap<_, Blake2_128Concat, ExternalCurrency, TokenId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn asset_rate_limit)]
+	/// Maximum amount of each asset minted per window
+	pub type AssetRateLimits<T: Config> = StorageMap<_, Twox64Concat, TokenId, RateLimit>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn destination_rate_limit)]
+	/// Maximum amount minted to any single destination address per window
+	pub type DestinationRateLimit<T: Config> = StorageValue<_, RateLimit, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn asset_mint_window)]
+	/// Amount of each rate limited asset minted in its current window
+	pub type AssetMintWindows<T: Config> =
+		StorageMap<_, Twox64Concat, TokenId, MintWindow<T::BlockNumber>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn destination_mint_window)]
+	/// Amount minted to each destination address in its current window, while
+	/// `DestinationRateLimit` is set
+	pub type DestinationMintWindows<T: Config> =
+		StorageMap<_, Blake2_128Concat, Address, MintWindow<T::BlockNumber>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reserve_report)]
+	/// Latest door account balance reported for each bridged currency
+	pub type ReserveReports<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ExternalCurrency,
+		ReserveReport<T::AccountId, T::BlockNumber>,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reserve_attestation)]
+	/// Latest door account balance reported by each relayer for each bridged currency, until
+	/// `RelayerThreshold` relayers report the same balance at the same ledger index
+	/// Reports of inactive relayers, or at a ledger index the recorded report already covers,
+	/// are dropped by the next report
+	pub type ReserveAttestations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ExternalCurrency,
+		Blake2_128Concat,
+		T::AccountId,
+		(LedgerIndex, Balance),
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn fee_schedule)]
+	/// Fee deducted from every inbound payment when it is minted
+	pub type BridgeFeeSchedule<T: Config> = StorageValue<_, FeeSchedule, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn minimum_fee)]
+	/// Minimum fee deducted from the inbound payments of each asset
+	pub type MinimumFees<T: Config> = StorageMap<_, Twox64Concat, TokenId, Balance>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn relayer_earnings)]
+	/// Fees of each asset paid to each relayer so far
+	pub type RelayerEarnings<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		TokenId,
+		Balance,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bridge_mode)]
+	/// Parts of the bridge currently paused by `PauseOrigin`
+	pub type CurrentBridgeMode<T: Config> = StorageValue<_, BridgeMode, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_withdrawal_id)]
+	/// Id to be assigned to the next withdrawal request
+	pub type NextWithdrawalId<T: Config> = StorageValue<_, WithdrawalId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn withdrawal_requests)]
+	/// Outbound withdrawals waiting to be (or already) paid out on the external ledger
+	pub type WithdrawalRequests<T: Config> =
+		StorageMap<_, Twox64Concat, WithdrawalId, WithdrawalRequest<T::AccountId>>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		TransactionAdded(LedgerIndex, TxHash),
+		/// Transaction was scheduled to be processed once its challenge period ends
+		TransactionAdded(LedgerIndex, TxHash, ProcessAt<T::BlockNumber>),
+		TransactionAttested(LedgerIndex, TxHash, T::AccountId),
+		/// Transaction can be submitted past `MaxTransactionAge`
+		TransactionAdmitted(TxHash),
+		AttestationMismatch(LedgerIndex, TxHash, T::AccountId),
+		RelayerThresholdSet(u32),
+		/// Challenge period tiers were replaced (amount threshold, challenge period)
+		ChallengePeriodTiersSet(Vec<(Balance, u32)>),
+		CurrencyRegistered(ExternalCurrency, TokenId),
+		CurrencyDeregistered(ExternalCurrency),
+		/// Transaction was challenged (challenger, bond, reason)
+		ChallengeSubmitted(TxHash, T::AccountId, Balance, ChallengeReason),
+		/// Challenge was wrong, the bond was slashed to the relayer (challenger, relayer, bond)
+		ChallengeRejected(TxHash, T::AccountId, T::AccountId, Balance),
+		/// Challenge was right, the bond was returned with a reward (challenger, bond, reward)
+		ChallengeAccepted(TxHash, T::AccountId, Balance, Balance),
+		/// Challenge was not resolved in time, the bond was returned and the policy applied
+		/// (challenger, bond, policy)
+		ChallengeExpired(TxHash, T::AccountId, Balance, ChallengeExpiry),
 		Processed(LedgerIndex, TxHash),
+		/// Transaction was proven invalid by a challenge and will not be processed
+		TransactionRejected(LedgerIndex, TxHash),
+		/// Minting the transaction failed, it can be retried with `retry_failed`
+		MintFailed(LedgerIndex, TxHash, DispatchError),
+		/// Minting the transaction would exceed a rate limit, it was deferred to the given block
+		MintDeferred(LedgerIndex, TxHash, RateLimitScope, T::BlockNumber),
+		/// Transaction exceeds a rate limit on its own and failed, it can be retried with
+		/// `retry_failed` once the limit is raised
+		MintExceedsRateLimit(LedgerIndex, TxHash, RateLimitScope),
+		AssetRateLimitSet(TokenId, Option<RateLimit>),
+		DestinationRateLimitSet(Option<RateLimit>),
 		RelayerAdded(T::AccountId),
 		RelayerRemoved(T::AccountId),
+		RelayerBonded(T::AccountId, Balance),
+		/// Stake started unbonding (relayer, amount, unlock block)
+		RelayerUnbonding(T::AccountId, Balance, T::BlockNumber),
+		RelayerUnbonded(T::AccountId, Balance),
+		RelayerSlashed(T::AccountId, TxHash, Balance),
+		/// Relayer stake fell below `RelayerMinStake`
+		RelayerDeactivated(T::AccountId),
+		FeeScheduleSet(FeeSchedule),
+		MinimumFeeSet(TokenId, Option<Balance>),
+		/// Bridge fee was deducted from a payment (asset, relayer, relayer fee, treasury fee)
+		FeePaid(TxHash, TokenId, T::AccountId, Balance, Balance),
+		/// Parts of the bridge were paused or resumed
+		BridgeModeSet(BridgeMode),
+		/// Relayer reported the door account balance, waiting for the quorum
+		/// (ledger index, reserve, relayer)
+		ReserveAttested(ExternalCurrency, LedgerIndex, Balance, T::AccountId),
+		/// Door account balance was reported by the quorum (ledger index, reserve, supply)
+		ReserveReported(ExternalCurrency, LedgerIndex, Balance, Balance),
+		/// Reserve backs less than the supply of the asset beyond `ReserveTolerance`, inbound
+		/// processing was paused (reserve, supply)
+		ReserveShortfall(ExternalCurrency, TokenId, Balance, Balance),
+		WithdrawalRequested(WithdrawalId, T::AccountId, Balance, Address),
+		WithdrawalExecuted(WithdrawalId, LedgerIndex, TxHash),
 	}
 
 	// Errors inform users that something went wrong.
@@ -115,32 +509,109 @@ pub mod pallet {
 		NotPermitted,
 		RelayerDoesNotExists,
 		TxReplay,
+		InvalidAmount,
+		UnknownWithdrawal,
+		WithdrawalAlreadyExecuted,
+		AlreadyAttested,
+		TooManyAttestations,
+		InvalidThreshold,
+		AlreadyChallenged,
+		ChallengeNotFound,
+		InsufficientStake,
+		NoUnbondedStake,
+		UnknownCurrency,
+		TransactionNotFailed,
+		UnknownTransaction,
+		ChallengePeriodOver,
+		EvidenceTooLong,
+		TooManyTransactions,
+		TooManyChallenges,
+		TimestampInFuture,
+		TimestampTooOld,
+		TooManyChallengePeriodTiers,
+		UnsortedChallengePeriodTiers,
+		InvalidRateLimit,
+		RateLimitExceeded,
+		InboundSubmissionPaused,
+		InboundProcessingPaused,
+		ChallengesPaused,
+		OutboundPaused,
+		StaleReserveReport,
+		InvalidFeeSchedule,
+		RelayerBanned,
+		TooManyRelayers,
+		RegistrationClosed,
+		InvalidChallengePeriod,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
 		fn on_initialize(n: T::BlockNumber) -> Weight {
-			let weights = Self::process_tx(n);
-			weights + Self::clear_storages(n)
+			let weights = Self::expire_challenges(n);
+			weights + Self::process_tx(n) + Self::clear_storages(n)
+		}
+
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			if Self::carry_over_depth() == 0 || Self::bridge_mode().inbound_processing_paused {
+				return Weight::zero()
+			}
+			let relayers = T::MaxRelayers::get();
+			let base = T::WeightInfo::process_tx(0, relayers).ref_time();
+			let per_transaction =
+				T::WeightInfo::process_tx(1, relayers).ref_time().saturating_sub(base);
+			let limit = remaining_weight.ref_time().saturating_sub(base) / per_transaction.max(1);
+			if limit == 0 {
+				return Weight::zero()
+			}
+			let processed = Self::drain_carry_over(n, limit.min(u32::MAX as u64) as u32);
+			T::WeightInfo::process_tx(processed, relayers)
+		}
+
+		fn offchain_worker(n: T::BlockNumber) {
+			if let Err(error) = Self::relay_ledgers(n) {
+				log::warn!(target: "runtime::bridge", "failed to relay ledgers: {:?}", error);
+			}
+			if let Err(error) = Self::watch_transactions(n) {
+				log::warn!(target: "runtime::bridge", "failed to verify transactions: {:?}", error);
+			}
 		}
 	}
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub relayers: Vec<T::AccountId>,
+		pub relayer_threshold: u32,
+		pub currencies: Vec<(ExternalCurrency, TokenId)>,
 	}
 
 	#[cfg(feature = "std")]
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			Self { relayers: vec![] }
+			Self {
+				relayers: vec![],
+				relayer_threshold: DefaultRelayerThreshold::get(),
+				currencies: vec![],
+			}
 		}
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
+			assert!(self.relayer_threshold > 0, "Relayer threshold must be greater than zero");
+			assert!(
+				self.relayers.len() as u32 <= T::MaxRelayers::get(),
+				"Genesis relayers must not exceed MaxRelayers"
+			);
+			for relayer in &self.relayers {
+				Pallet::<T>::bond(relayer, T::RelayerMinStake::get())
+					.expect("Genesis relayers must be able to bond RelayerMinStake");
+			}
 			Pallet::<T>::initialize_relayer(&self.relayers);
+			RelayerThreshold::<T>::put(self.relayer_threshold);
+			for (currency, asset_id) in &self.currencies {
+				CurrencyRegistry::<T>::insert(currency, asset_id);
+			}
 		}
 	}
 
@@ -149,8 +620,9 @@ pub mod pallet {
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
+		#[pallet::weight(T::WeightInfo::submit_transaction(T::MaxRelayers::get()))]
 		/// submit a transaction to mint tokens to user account
+		/// The transaction is scheduled once `RelayerThreshold` relayers attested to the same data
 		pub fn submit_transaction(
 			origin: OriginFor<T>,
 			ledger_index: LedgerIndex,
@@ -158,126 +630,1079 @@ pub mod pallet {
 			transaction: TxData,
 			timestamp: Timestamp,
 		) -> DispatchResult {
-			let relayer = ensure_signed(origin)?;
-			let active_relayer = <Relayer<T>>::get(relayer).unwrap_or(false);
-			ensure!(active_relayer, Error::<T>::NotPermitted);
+			let relayer = Self::ensure_relayer(origin)?;
+			ensure!(
+				!Self::bridge_mode().inbound_submission_paused,
+				Error::<T>::InboundSubmissionPaused
+			);
 			ensure!(
-				Self::process_transaction_details(transaction_hash).is_none(),
+				matches!(
+					Self::transaction_status(transaction_hash),
+					None | Some(TxStatus::Rejected)
+				),
 				Error::<T>::TxReplay
 			);
-			Self::add_to_relay(relayer, ledger_index, transaction_hash, transaction, timestamp)
+			ensure!(!Self::attested(transaction_hash, &relayer), Error::<T>::AlreadyAttested);
+			let now = Self::unix_now();
+			ensure!(
+				timestamp <= now.saturating_add(T::MaxTimestampDrift::get()),
+				Error::<T>::TimestampInFuture
+			);
+			ensure!(
+				now.saturating_sub(timestamp) <= T::MaxTransactionAge::get() ||
+					AdmittedTransactions::<T>::contains_key(transaction_hash),
+				Error::<T>::TimestampTooOld
+			);
+			match transaction {
+				TxData::Payment { currency, .. } => ensure!(
+					CurrencyRegistry::<T>::contains_key(currency),
+					Error::<T>::UnknownCurrency
+				),
+			}
+			Self::attest(relayer, ledger_index, transaction_hash, transaction, timestamp)
 		}
 
-		/// Submit transaction challenge
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn submit_challenge(origin: OriginFor<T>, transaction_hash: TxHash) -> DispatchResult {
+		/// Submit transaction challenge, reserving `ChallengeBond` from the challenger
+		/// Only transactions still in their challenge period can be challenged
+		#[pallet::weight(T::WeightInfo::submit_challenge())]
+		pub fn submit_challenge(
+			origin: OriginFor<T>,
+			transaction_hash: TxHash,
+			reason: ChallengeReason,
+			evidence: Option<Vec<u8>>,
+		) -> DispatchResult {
 			let challenger = ensure_signed(origin)?;
-			ChallengeTransactionList::<T>::insert(transaction_hash, challenger);
+			ensure!(!Self::bridge_mode().challenges_paused, Error::<T>::ChallengesPaused);
+			let process_at = match Self::transaction_status(transaction_hash) {
+				None => return Err(Error::<T>::UnknownTransaction.into()),
+				Some(TxStatus::Challenged(_)) => return Err(Error::<T>::AlreadyChallenged.into()),
+				Some(TxStatus::Pending(process_at))
+					if !Self::is_due(process_at, <frame_system::Pallet<T>>::block_number()) =>
+					process_at,
+				Some(_) => return Err(Error::<T>::ChallengePeriodOver.into()),
+			};
+			let evidence: Option<BoundedVec<u8, T::MaxEvidenceLength>> = match evidence {
+				Some(evidence) =>
+					Some(evidence.try_into().map_err(|_| Error::<T>::EvidenceTooLong)?),
+				None => None,
+			};
+			let bond = T::ChallengeBond::get();
+			T::Currency::reserve(&challenger, bond)?;
+			let expires_at = <frame_system::Pallet<T>>::block_number() +
+				T::ChallengeResolutionPeriod::get().into();
+			ChallengeTransactionList::<T>::insert(
+				transaction_hash,
+				Challenge { challenger, bond, reason, evidence, expires_at },
+			);
+			ChallengeExpiries::<T>::try_append(expires_at, transaction_hash)
+				.map_err(|_| Error::<T>::TooManyChallenges)?;
+			TransactionStatus::<T>::insert(transaction_hash, TxStatus::Challenged(process_at));
+			Self::deposit_event(Event::<T>::ChallengeSubmitted(
+				transaction_hash,
+				challenger,
+				bond,
+				reason,
+			));
 			Ok(())
 		}
 
 		/// Sudo verifies that the challenge failed
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
+		/// The challenger bond is slashed to the relayer of the transaction, which is processed
+		/// at its scheduled block or rescheduled if that block has passed
+		#[pallet::weight(T::WeightInfo::failed_challenge())]
 		pub fn failed_challenge(origin: OriginFor<T>, transaction_hash: TxHash) -> DispatchResult {
 			T::ApproveOrigin::ensure_origin(origin)?;
-			ChallengeTransactionList::<T>::remove(transaction_hash);
-			Self::add_to_process(transaction_hash)?;
+			let process_at = Self::challenged_process_at(transaction_hash)?;
+			let Challenge { challenger, bond, .. } =
+				ChallengeTransactionList::<T>::take(transaction_hash)
+					.ok_or(Error::<T>::ChallengeNotFound)?;
+			let (_, _, relayer) = ProcessTransactionDetails::<T>::get(transaction_hash)
+				.ok_or(Error::<T>::ChallengeNotFound)?;
+			let unmoved =
+				T::Currency::repatriate_reserved(&challenger, &relayer, bond, BalanceStatus::Free)?;
+			Self::release(transaction_hash, process_at);
+			Self::deposit_event(Event::<T>::ChallengeRejected(
+				transaction_hash,
+				challenger,
+				relayer,
+				bond.saturating_sub(unmoved),
+			));
 			Ok(())
 		}
 
 		/// Sudo verifies that the challenge is true
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn success_challenge(origin: OriginFor<T>, transaction_hash: TxHash) -> DispatchResult {
+		/// The challenger bond is returned together with `ChallengeReward`, every relayer that
+		/// attested to the transaction is slashed and the transaction is rejected
+		/// Weighted for `MaxRelayers` attestations, refunded down to the attestations stored
+		#[pallet::weight(T::WeightInfo::success_challenge(T::MaxRelayers::get()))]
+		pub fn success_challenge(
+			origin: OriginFor<T>,
+			transaction_hash: TxHash,
+		) -> DispatchResultWithPostInfo {
 			T::ApproveOrigin::ensure_origin(origin)?;
-			ProcessTransactionDetails::<T>::remove(transaction_hash);
-			Ok(())
+			let process_at = Self::challenged_process_at(transaction_hash)?;
+			let Challenge { challenger, bond, .. } =
+				ChallengeTransactionList::<T>::take(transaction_hash)
+					.ok_or(Error::<T>::ChallengeNotFound)?;
+			let (ledger_index, transaction, _) =
+				ProcessTransactionDetails::<T>::get(transaction_hash)
+					.ok_or(Error::<T>::ChallengeNotFound)?;
+			let attestation = (ledger_index, transaction);
+			let mut attestations = 0;
+			for (relayer, attested) in TransactionAttestations::<T>::iter_prefix(transaction_hash) {
+				attestations += 1;
+				if attested == attestation {
+					Self::slash_relayer(&relayer, transaction_hash);
+				}
+			}
+			Self::reject(transaction_hash, process_at);
+
+			let returned = bond.saturating_sub(T::Currency::unreserve(&challenger, bond));
+			let reward = Self::pay_reward(&challenger, T::ChallengeReward::get());
+			Self::deposit_event(Event::<T>::ChallengeAccepted(
+				transaction_hash,
+				challenger,
+				returned,
+				reward,
+			));
+			Ok(Some(T::WeightInfo::success_challenge(attestations)).into())
 		}
 
-		/// add a relayer
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		/// add a relayer, bonding `RelayerMinStake` from its account
+		/// Relayers removed before are allowed to register again
+		#[pallet::weight(T::WeightInfo::add_relayer())]
 		pub fn add_relayer(origin: OriginFor<T>, relayer: T::AccountId) -> DispatchResult {
 			T::ApproveOrigin::ensure_origin(origin)?;
+			Self::ensure_relayer_room(&relayer)?;
+			let active = Self::relayer_bond(relayer).map(|bond| bond.active).unwrap_or_default();
+			Self::bond(&relayer, T::RelayerMinStake::get().saturating_sub(active))?;
 			Self::initialize_relayer(&vec![relayer]);
+			RemovedRelayers::<T>::remove(relayer);
 			Self::deposit_event(Event::<T>::RelayerAdded(relayer));
 			Ok(())
 		}
 
-		/// remove a relayer
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
+		/// remove a relayer, its whole stake starts unbonding
+		/// It can not register again until it is added back by `ApproveOrigin`
+		#[pallet::weight(T::WeightInfo::remove_relayer())]
 		pub fn remove_relayer(origin: OriginFor<T>, relayer: T::AccountId) -> DispatchResult {
 			T::ApproveOrigin::ensure_origin(origin)?;
 			if <Relayer<T>>::contains_key(relayer) {
 				<Relayer<T>>::remove(relayer);
+				RelayerCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+				RemovedRelayers::<T>::insert(relayer, ());
+				let active =
+					Self::relayer_bond(relayer).map(|bond| bond.active).unwrap_or_default();
+				Self::unbond(&relayer, active);
 				Self::deposit_event(Event::<T>::RelayerRemoved(relayer));
 				Ok(())
 			} else {
 				Err(Error::<T>::RelayerDoesNotExists.into())
 			}
 		}
+
+		/// register the caller as a relayer by bonding `amount`, or top up an existing stake
+		/// The bonded stake must reach `RelayerMinStake`, relayers removed by `ApproveOrigin` can
+		/// not register again
+		/// New relayers can only register while `RelayerThreshold` is greater than 1, so that no
+		/// single staked account can schedule transactions on its own
+		#[pallet::weight(T::WeightInfo::register_relayer())]
+		pub fn register_relayer(origin: OriginFor<T>, amount: Balance) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			ensure!(!RemovedRelayers::<T>::contains_key(relayer), Error::<T>::RelayerBanned);
+			if !<Relayer<T>>::contains_key(relayer) {
+				ensure!(Self::relayer_threshold() > 1, Error::<T>::RegistrationClosed);
+				Self::ensure_relayer_room(&relayer)?;
+			}
+			Self::bond(&relayer, amount)?;
+			let active = Self::relayer_bond(relayer).map(|bond| bond.active).unwrap_or_default();
+			ensure!(active >= T::RelayerMinStake::get(), Error::<T>::InsufficientStake);
+			if !Self::get_relayer(relayer).unwrap_or(false) {
+				Self::initialize_relayer(&vec![relayer]);
+				Self::deposit_event(Event::<T>::RelayerAdded(relayer));
+			}
+			Ok(())
+		}
+
+		/// start unbonding `amount` of the caller's relayer stake
+		/// The relayer is deactivated if its stake falls below `RelayerMinStake`
+		#[pallet::weight(T::WeightInfo::unbond_relayer())]
+		pub fn unbond_relayer(origin: OriginFor<T>, amount: Balance) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			let active = Self::relayer_bond(relayer).map(|bond| bond.active).unwrap_or_default();
+			ensure!(amount > 0 && amount <= active, Error::<T>::InsufficientStake);
+			Self::unbond(&relayer, amount);
+			Ok(())
+		}
+
+		/// withdraw relayer stake whose unbonding period is over
+		#[pallet::weight(T::WeightInfo::withdraw_unbonded())]
+		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			let amount = RelayerBonds::<T>::try_mutate_exists(
+				relayer,
+				|maybe_bond| -> Result<Balance, DispatchError> {
+					let bond = maybe_bond.as_mut().ok_or(Error::<T>::NoUnbondedStake)?;
+					ensure!(
+						bond.unbonding > 0 && bond.unlock_at <= now,
+						Error::<T>::NoUnbondedStake
+					);
+					let amount = bond.unbonding;
+					bond.unbonding = 0;
+					if bond.total() == 0 {
+						*maybe_bond = None;
+					}
+					Ok(amount)
+				},
+			)?;
+			T::Currency::unreserve(&relayer, amount);
+			Self::deposit_event(Event::<T>::RelayerUnbonded(relayer, amount));
+			Ok(())
+		}
+
+		/// set the number of matching relayer attestations required to schedule a transaction or
+		/// to record a reserve report
+		/// A threshold of 1 closes `register_relayer` to new relayers
+		#[pallet::weight(T::WeightInfo::set_relayer_threshold())]
+		pub fn set_relayer_threshold(origin: OriginFor<T>, threshold: u32) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			ensure!(threshold > 0, Error::<T>::InvalidThreshold);
+			RelayerThreshold::<T>::put(threshold);
+			Self::deposit_event(Event::<T>::RelayerThresholdSet(threshold));
+			Ok(())
+		}
+
+		/// replace the table of challenge periods applied from each payment amount threshold
+		/// Thresholds must be strictly ascending and periods greater than zero
+		#[pallet::weight(T::WeightInfo::set_challenge_period_tiers(tiers.len() as u32))]
+		pub fn set_challenge_period_tiers(
+			origin: OriginFor<T>,
+			tiers: Vec<(Balance, u32)>,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			ensure!(
+				tiers.windows(2).all(|pair| pair[0].0 < pair[1].0),
+				Error::<T>::UnsortedChallengePeriodTiers
+			);
+			ensure!(
+				tiers.iter().all(|(_, period)| *period > 0),
+				Error::<T>::InvalidChallengePeriod
+			);
+			let bounded: BoundedVec<(Balance, u32), T::MaxChallengePeriodTiers> =
+				tiers.clone().try_into().map_err(|_| Error::<T>::TooManyChallengePeriodTiers)?;
+			ChallengePeriodTiers::<T>::put(bounded);
+			Self::deposit_event(Event::<T>::ChallengePeriodTiersSet(tiers));
+			Ok(())
+		}
+
+		/// set or remove the maximum amount of an asset minted per window
+		#[pallet::weight(T::WeightInfo::set_asset_rate_limit())]
+		pub fn set_asset_rate_limit(
+			origin: OriginFor<T>,
+			asset_id: TokenId,
+			limit: Option<RateLimit>,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			match limit {
+				Some(rate_limit) => {
+					ensure!(rate_limit.period > 0, Error::<T>::InvalidRateLimit);
+					AssetRateLimits::<T>::insert(asset_id, rate_limit);
+				},
+				None => {
+					AssetRateLimits::<T>::remove(asset_id);
+					AssetMintWindows::<T>::remove(asset_id);
+				},
+			}
+			Self::deposit_event(Event::<T>::AssetRateLimitSet(asset_id, limit));
+			Ok(())
+		}
+
+		/// set or remove the maximum amount minted to a single destination address per window
+		#[pallet::weight(T::WeightInfo::set_destination_rate_limit())]
+		pub fn set_destination_rate_limit(
+			origin: OriginFor<T>,
+			limit: Option<RateLimit>,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			if let Some(rate_limit) = limit {
+				ensure!(rate_limit.period > 0, Error::<T>::InvalidRateLimit);
+			}
+			DestinationRateLimit::<T>::set(limit);
+			Self::deposit_event(Event::<T>::DestinationRateLimitSet(limit));
+			Ok(())
+		}
+
+		/// pause or resume parts of the bridge, queued transactions keep their place while
+		/// processing is paused
+		#[pallet::weight(T::WeightInfo::set_bridge_mode())]
+		pub fn set_bridge_mode(origin: OriginFor<T>, mode: BridgeMode) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			CurrentBridgeMode::<T>::put(mode);
+			Self::deposit_event(Event::<T>::BridgeModeSet(mode));
+			Ok(())
+		}
+
+		/// set the fee deducted from inbound payments
+		#[pallet::weight(T::WeightInfo::set_fee_schedule())]
+		pub fn set_fee_schedule(origin: OriginFor<T>, schedule: FeeSchedule) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			ensure!(schedule.basis_points <= 10_000, Error::<T>::InvalidFeeSchedule);
+			BridgeFeeSchedule::<T>::put(schedule);
+			Self::deposit_event(Event::<T>::FeeScheduleSet(schedule));
+			Ok(())
+		}
+
+		/// set or remove the minimum fee deducted from the inbound payments of an asset
+		#[pallet::weight(T::WeightInfo::set_minimum_fee())]
+		pub fn set_minimum_fee(
+			origin: OriginFor<T>,
+			asset_id: TokenId,
+			minimum: Option<Balance>,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			MinimumFees::<T>::set(asset_id, minimum);
+			Self::deposit_event(Event::<T>::MinimumFeeSet(asset_id, minimum));
+			Ok(())
+		}
+
+		/// map a currency of the external ledger to the asset minted for it
+		#[pallet::weight(T::WeightInfo::register_currency())]
+		pub fn register_currency(
+			origin: OriginFor<T>,
+			currency: ExternalCurrency,
+			asset_id: TokenId,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			CurrencyRegistry::<T>::insert(currency, asset_id);
+			Self::deposit_event(Event::<T>::CurrencyRegistered(currency, asset_id));
+			Ok(())
+		}
+
+		/// stop bridging a currency of the external ledger
+		#[pallet::weight(T::WeightInfo::deregister_currency())]
+		pub fn deregister_currency(
+			origin: OriginFor<T>,
+			currency: ExternalCurrency,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			CurrencyRegistry::<T>::take(currency).ok_or(Error::<T>::UnknownCurrency)?;
+			Self::deposit_event(Event::<T>::CurrencyDeregistered(currency));
+			Ok(())
+		}
+
+		/// retry minting a transaction that failed when processed
+		#[pallet::weight(T::WeightInfo::retry_failed())]
+		pub fn retry_failed(origin: OriginFor<T>, transaction_hash: TxHash) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(
+				!Self::bridge_mode().inbound_processing_paused,
+				Error::<T>::InboundProcessingPaused
+			);
+			FailedTransactions::<T>::take(transaction_hash)
+				.ok_or(Error::<T>::TransactionNotFailed)?;
+			let (ledger_index, tx, relayer) = ProcessTransactionDetails::<T>::get(transaction_hash)
+				.ok_or(Error::<T>::TransactionNotFailed)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				Self::rate_limited(now, &tx.transaction).is_none(),
+				Error::<T>::RateLimitExceeded
+			);
+			Self::mint(transaction_hash, &tx.transaction, &relayer)?;
+			Self::record_mint(now, &tx.transaction);
+			Self::settle(ledger_index, transaction_hash);
+			Ok(())
+		}
+
+		/// accept submissions of a transaction older than `MaxTransactionAge`
+		#[pallet::weight(T::WeightInfo::admit_transaction())]
+		pub fn admit_transaction(origin: OriginFor<T>, transaction_hash: TxHash) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			ensure!(
+				matches!(
+					Self::transaction_status(transaction_hash),
+					None | Some(TxStatus::Rejected)
+				),
+				Error::<T>::TxReplay
+			);
+			AdmittedTransactions::<T>::insert(transaction_hash, ());
+			Self::deposit_event(Event::<T>::TransactionAdmitted(transaction_hash));
+			Ok(())
+		}
+
+		/// burn tokens and request a payout to `destination` on the external ledger
+		#[pallet::weight(T::WeightInfo::withdraw())]
+		pub fn withdraw(
+			origin: OriginFor<T>,
+			amount: Balance,
+			destination: Address,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::bridge_mode().outbound_paused, Error::<T>::OutboundPaused);
+			ensure!(amount > 0, Error::<T>::InvalidAmount);
+			let amount = T::Assets::burn_from(T::AssetId::get(), &who, amount)?;
+
+			let withdrawal_id = NextWithdrawalId::<T>::mutate(|id| {
+				let current = *id;
+				*id = id.saturating_add(1);
+				current
+			});
+			WithdrawalRequests::<T>::insert(
+				withdrawal_id,
+				WithdrawalRequest { who, amount, destination, status: WithdrawalStatus::Requested },
+			);
+			Self::deposit_event(Event::<T>::WithdrawalRequested(
+				withdrawal_id,
+				who,
+				amount,
+				destination,
+			));
+			Ok(())
+		}
+
+		/// Relayer confirms that a withdrawal was paid out on the external ledger
+		#[pallet::weight(T::WeightInfo::execute_withdrawal())]
+		pub fn execute_withdrawal(
+			origin: OriginFor<T>,
+			withdrawal_id: WithdrawalId,
+			ledger_index: LedgerIndex,
+			transaction_hash: TxHash,
+		) -> DispatchResult {
+			Self::ensure_relayer(origin)?;
+			WithdrawalRequests::<T>::try_mutate(withdrawal_id, |request| -> DispatchResult {
+				let request = request.as_mut().ok_or(Error::<T>::UnknownWithdrawal)?;
+				ensure!(
+					request.status == WithdrawalStatus::Requested,
+					Error::<T>::WithdrawalAlreadyExecuted
+				);
+				request.status = WithdrawalStatus::Executed { ledger_index, transaction_hash };
+				Ok(())
+			})?;
+			Self::deposit_event(Event::<T>::WithdrawalExecuted(
+				withdrawal_id,
+				ledger_index,
+				transaction_hash,
+			));
+			Ok(())
+		}
+
+		/// Staked relayer reports the balance of the door account on the external ledger for a
+		/// currency at `ledger_index`
+		/// The balance is recorded once `RelayerThreshold` active relayers report it at the same
+		/// ledger index, and inbound processing is paused if it backs less than the supply of the
+		/// bridged asset beyond `ReserveTolerance`
+		/// Weighted for `MaxRelayers` reports, refunded down to the reports stored
+		#[pallet::weight(T::WeightInfo::report_reserve(T::MaxRelayers::get()))]
+		pub fn report_reserve(
+			origin: OriginFor<T>,
+			currency: ExternalCurrency,
+			ledger_index: LedgerIndex,
+			reserve: Balance,
+		) -> DispatchResultWithPostInfo {
+			let relayer = Self::ensure_relayer(origin)?;
+			let stake = Self::relayer_bond(relayer).map(|bond| bond.active).unwrap_or_default();
+			ensure!(stake >= T::RelayerMinStake::get(), Error::<T>::InsufficientStake);
+			let asset_id = Self::currency_registry(currency).ok_or(Error::<T>::UnknownCurrency)?;
+			let reported = Self::reserve_report(currency).map(|report| report.ledger_index);
+			if let Some(reported) = reported {
+				ensure!(ledger_index > reported, Error::<T>::StaleReserveReport);
+			}
+
+			let attestation = (ledger_index, reserve);
+			let mut matching: u32 = 1;
+			let mut attestations = 0;
+			let mut dropped = Vec::new();
+			for (other_relayer, other) in ReserveAttestations::<T>::iter_prefix(currency) {
+				if other_relayer == relayer {
+					continue
+				}
+				if !Self::get_relayer(other_relayer).unwrap_or(false) ||
+					reported.map_or(false, |reported| other.0 <= reported)
+				{
+					dropped.push(other_relayer);
+					continue
+				}
+				attestations += 1;
+				if other == attestation {
+					matching += 1;
+				}
+			}
+			ensure!(attestations < T::MaxRelayers::get(), Error::<T>::TooManyAttestations);
+			for other_relayer in dropped {
+				ReserveAttestations::<T>::remove(currency, other_relayer);
+			}
+			let weight = T::WeightInfo::report_reserve(attestations);
+			if matching < Self::relayer_threshold() {
+				ReserveAttestations::<T>::insert(currency, relayer, attestation);
+				Self::deposit_event(Event::<T>::ReserveAttested(
+					currency,
+					ledger_index,
+					reserve,
+					relayer,
+				));
+				return Ok(Some(weight).into())
+			}
+			let _ = ReserveAttestations::<T>::clear_prefix(currency, u32::MAX, None);
+
+			let supply = T::Assets::total_issuance(asset_id);
+			ReserveReports::<T>::insert(
+				currency,
+				ReserveReport {
+					relayer,
+					ledger_index,
+					reserve,
+					supply,
+					reported_at: <frame_system::Pallet<T>>::block_number(),
+				},
+			);
+			Self::deposit_event(Event::<T>::ReserveReported(
+				currency,
+				ledger_index,
+				reserve,
+				supply,
+			));
+
+			if supply.saturating_sub(reserve) > T::ReserveTolerance::get() * supply {
+				Self::deposit_event(Event::<T>::ReserveShortfall(
+					currency, asset_id, reserve, supply,
+				));
+				let mut mode = Self::bridge_mode();
+				if !mode.inbound_processing_paused {
+					mode.inbound_processing_paused = true;
+					CurrentBridgeMode::<T>::put(mode);
+					Self::deposit_event(Event::<T>::BridgeModeSet(mode));
+				}
+			}
+			Ok(Some(weight).into())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
 	pub fn initialize_relayer(relayers: &Vec<T::AccountId>) {
 		for relayer in relayers {
+			if !<Relayer<T>>::contains_key(relayer) {
+				RelayerCount::<T>::mutate(|count| *count = count.saturating_add(1));
+			}
 			<Relayer<T>>::insert(relayer, true);
 		}
 	}
 
+	/// Ensure `relayer` is already known or there is room left for it under `MaxRelayers`
+	fn ensure_relayer_room(relayer: &T::AccountId) -> DispatchResult {
+		ensure!(
+			<Relayer<T>>::contains_key(relayer) || Self::relayer_count() < T::MaxRelayers::get(),
+			Error::<T>::TooManyRelayers
+		);
+		Ok(())
+	}
+
+	/// Reserve `amount` from `relayer` and add it to its active stake
+	pub fn bond(relayer: &T::AccountId, amount: Balance) -> DispatchResult {
+		if amount == 0 {
+			return Ok(())
+		}
+		T::Currency::reserve(relayer, amount)?;
+		RelayerBonds::<T>::mutate(relayer, |bond| {
+			let bond = bond.get_or_insert_with(Default::default);
+			bond.active = bond.active.saturating_add(amount);
+		});
+		Self::deposit_event(Event::<T>::RelayerBonded(*relayer, amount));
+		Ok(())
+	}
+
+	/// Move up to `amount` of the active stake of `relayer` to unbonding
+	/// Unbonding restarts the delay for the whole unbonding stake
+	pub fn unbond(relayer: &T::AccountId, amount: Balance) {
+		let mut bond = match Self::relayer_bond(relayer) {
+			Some(bond) => bond,
+			None => return,
+		};
+		let amount = amount.min(bond.active);
+		if amount == 0 {
+			return
+		}
+		let unlock_at =
+			<frame_system::Pallet<T>>::block_number() + T::RelayerUnbondingPeriod::get().into();
+		bond.active -= amount;
+		bond.unbonding = bond.unbonding.saturating_add(amount);
+		bond.unlock_at = unlock_at;
+		let active = bond.active;
+		RelayerBonds::<T>::insert(relayer, bond);
+		Self::deposit_event(Event::<T>::RelayerUnbonding(*relayer, amount, unlock_at));
+		Self::deactivate_if_underbonded(relayer, active);
+	}
+
+	/// Slash `RelayerSlash` of the stake of `relayer` into the bridge account
+	/// The active stake is slashed first, then the unbonding one
+	pub fn slash_relayer(relayer: &T::AccountId, transaction_hash: TxHash) {
+		let mut bond = Self::relayer_bond(relayer).unwrap_or_default();
+		let amount = T::RelayerSlash::get() * bond.total();
+		let unmoved = T::Currency::repatriate_reserved(
+			relayer,
+			&Self::account_id(),
+			amount,
+			BalanceStatus::Free,
+		)
+		.unwrap_or(amount);
+		let slashed = amount.saturating_sub(unmoved);
+		let from_active = slashed.min(bond.active);
+		bond.active -= from_active;
+		bond.unbonding = bond.unbonding.saturating_sub(slashed - from_active);
+		let active = bond.active;
+		if bond.total() == 0 {
+			RelayerBonds::<T>::remove(relayer);
+		} else {
+			RelayerBonds::<T>::insert(relayer, bond);
+		}
+		Self::deposit_event(Event::<T>::RelayerSlashed(*relayer, transaction_hash, slashed));
+		Self::deactivate_if_underbonded(relayer, active);
+	}
+
+	fn deactivate_if_underbonded(relayer: &T::AccountId, active: Balance) {
+		if active < T::RelayerMinStake::get() && Self::get_relayer(relayer).unwrap_or(false) {
+			<Relayer<T>>::insert(relayer, false);
+			Self::deposit_event(Event::<T>::RelayerDeactivated(*relayer));
+		}
+	}
+
+	/// The account ID of the bridge, funding challenge rewards
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// Pay up to `amount` from the bridge account to `who`, returning the amount paid
+	pub fn pay_reward(who: &T::AccountId, amount: Balance) -> Balance {
+		let pot = Self::account_id();
+		let amount = amount.min(T::Currency::free_balance(&pot));
+		if amount == 0 {
+			return 0
+		}
+		match T::Currency::transfer(&pot, who, amount, ExistenceRequirement::AllowDeath) {
+			Ok(()) => amount,
+			Err(_) => 0,
+		}
+	}
+
+	/// Ensure the origin is signed by an active relayer
+	pub fn ensure_relayer(origin: OriginFor<T>) -> Result<T::AccountId, DispatchError> {
+		let relayer = ensure_signed(origin)?;
+		let active_relayer = <Relayer<T>>::get(relayer).unwrap_or(false);
+		ensure!(active_relayer, Error::<T>::NotPermitted);
+		Ok(relayer)
+	}
+
+	/// Process the transactions maturing at block `n` or by the current Unix time, after the
+	/// ones carried over from earlier blocks, up to `MaxProcessPerBlock` transactions
 	pub fn process_tx(n: T::BlockNumber) -> Weight {
-		let tx_items: Vec<TxHash> = match <ProcessTransaction<T>>::take(n) {
-			None => return DbWeight::get().reads(2),
-			Some(v) => v,
+		if Self::bridge_mode().inbound_processing_paused {
+			return Self::hold_matured(n)
+		}
+		let mut processed = Self::drain_carry_over(n, T::MaxProcessPerBlock::get());
+		let mut items = processed;
+		if let Some(tx_items) = <ProcessTransaction<T>>::take(n) {
+			items = items.saturating_add(tx_items.len() as u32);
+			Self::process_or_carry_over(n, tx_items, &mut processed);
+		}
+		let (timed_items, scanned) = Self::process_timed(n, &mut processed);
+		items = items.saturating_add(timed_items);
+		T::WeightInfo::process_tx(items, T::MaxRelayers::get())
+			.saturating_add(T::DbWeight::get().reads(scanned as u64))
+	}
+
+	/// Process the transactions scheduled up to the current Unix time, scanning at most
+	/// `MaxTimedScanPerBlock` seconds of `TimedProcessTransaction` per block
+	/// Returns the number of transactions met and of seconds scanned
+	fn process_timed(n: T::BlockNumber, processed: &mut u32) -> (u32, u32) {
+		let mut cursor = match Self::timed_process_cursor() {
+			None => return (0, 0),
+			Some(cursor) => cursor,
 		};
-		let mut reads = 2;
-		let mut writes = 0;
+		let now = Self::unix_now();
+		let mut items = 0u32;
+		let mut scanned = 0;
+		while cursor <= now && scanned < T::MaxTimedScanPerBlock::get() {
+			if let Some(tx_items) = <TimedProcessTransaction<T>>::take(cursor) {
+				items = items.saturating_add(tx_items.len() as u32);
+				Self::process_or_carry_over(n, tx_items, processed);
+			}
+			cursor += 1;
+			scanned += 1;
+		}
+		if scanned > 0 {
+			<TimedProcessCursor<T>>::put(cursor);
+		}
+		(items, scanned)
+	}
+
+	/// Process transactions until `MaxProcessPerBlock` is reached, carrying over the rest
+	fn process_or_carry_over(n: T::BlockNumber, tx_items: TxHashesOf<T>, processed: &mut u32) {
 		for transaction_hash in tx_items {
-			if !<ChallengeTransactionList<T>>::contains_key(transaction_hash) {
-				let tx_details = <ProcessTransactionDetails<T>>::get(transaction_hash);
-				reads += 1;
-				match tx_details {
-					None => {},
-					Some((ledger_index, ref tx, _relayer)) => {
-						match tx.transaction {
-							TxData::Payment { amount, address } => {
-								let _ = T::Assets::mint_into(
-									T::AssetId::get(),
-									&address.into(),
-									amount,
-								);
-								writes += 1;
-							},
-						}
-						let clear_block_number = <frame_system::Pallet<T>>::block_number() +
-							T::ClearTxPeriod::get().into();
-						<SettledTransactionDetails<T>>::append(
-							clear_block_number,
+			if *processed < T::MaxProcessPerBlock::get() {
+				Self::process_matured(n, transaction_hash);
+				*processed += 1;
+			} else {
+				Self::carry_over(transaction_hash);
+			}
+		}
+	}
+
+	/// Move the transactions maturing at block `n` behind the carried over ones, to be processed
+	/// in order once processing resumes
+	fn hold_matured(n: T::BlockNumber) -> Weight {
+		let tx_items = match <ProcessTransaction<T>>::take(n) {
+			None => return T::WeightInfo::process_tx(0, T::MaxRelayers::get()),
+			Some(tx_items) => tx_items,
+		};
+		let weight = T::WeightInfo::process_tx(tx_items.len() as u32, T::MaxRelayers::get());
+		for transaction_hash in tx_items {
+			Self::carry_over(transaction_hash);
+		}
+		weight
+	}
+
+	/// Process up to `limit` transactions from the head of `CarryOverQueue`
+	pub fn drain_carry_over(n: T::BlockNumber, limit: u32) -> u32 {
+		let (mut head, tail) = Self::carry_over_cursor();
+		let mut processed = 0;
+		while head < tail && processed < limit {
+			if let Some(transaction_hash) = <CarryOverQueue<T>>::take(head) {
+				Self::process_matured(n, transaction_hash);
+			}
+			head += 1;
+			processed += 1;
+		}
+		if processed > 0 {
+			<CarryOverCursor<T>>::put((head, tail));
+		}
+		processed
+	}
+
+	/// Number of matured transactions waiting in `CarryOverQueue`
+	pub fn carry_over_depth() -> u64 {
+		let (head, tail) = Self::carry_over_cursor();
+		tail.saturating_sub(head)
+	}
+
+	fn carry_over(transaction_hash: TxHash) {
+		<CarryOverCursor<T>>::mutate(|(_, tail)| {
+			<CarryOverQueue<T>>::insert(*tail, transaction_hash);
+			*tail += 1;
+		});
+	}
+
+	/// Mint a matured transaction, unless it is waiting for a challenge to be resolved
+	fn process_matured(n: T::BlockNumber, transaction_hash: TxHash) {
+		match Self::transaction_status(transaction_hash) {
+			Some(TxStatus::Pending(process_at)) if Self::is_due(process_at, n) => {},
+			Some(TxStatus::Deferred(block_number)) if block_number <= n => {},
+			_ => return,
+		}
+		let tx_details = <ProcessTransactionDetails<T>>::get(transaction_hash);
+		if let Some((ledger_index, ref tx, relayer)) = tx_details {
+			if let Some(scope) = Self::oversized(&tx.transaction) {
+				<FailedTransactions<T>>::insert(
+					transaction_hash,
+					DispatchError::from(Error::<T>::RateLimitExceeded),
+				);
+				<TransactionStatus<T>>::insert(transaction_hash, TxStatus::Failed);
+				Self::deposit_event(Event::MintExceedsRateLimit(
+					ledger_index,
+					transaction_hash,
+					scope,
+				));
+				return
+			}
+			if let Some((scope, available_at)) = Self::rate_limited(n, &tx.transaction) {
+				match Self::defer(transaction_hash, available_at) {
+					Some(deferred_to) => Self::deposit_event(Event::MintDeferred(
+						ledger_index,
+						transaction_hash,
+						scope,
+						deferred_to,
+					)),
+					None => {
+						let error = DispatchError::from(Error::<T>::TooManyTransactions);
+						<FailedTransactions<T>>::insert(transaction_hash, error);
+						<TransactionStatus<T>>::insert(transaction_hash, TxStatus::Failed);
+						Self::deposit_event(Event::MintFailed(
+							ledger_index,
 							transaction_hash,
-						);
-						writes += 1;
-						Self::deposit_event(Event::Processed(ledger_index, transaction_hash));
+							error,
+						));
 					},
 				}
+				return
+			}
+			match Self::mint(transaction_hash, &tx.transaction, &relayer) {
+				Ok(()) => {
+					Self::record_mint(n, &tx.transaction);
+					Self::settle(ledger_index, transaction_hash);
+				},
+				Err(error) => {
+					<FailedTransactions<T>>::insert(transaction_hash, error);
+					<TransactionStatus<T>>::insert(transaction_hash, TxStatus::Failed);
+					Self::deposit_event(Event::MintFailed(ledger_index, transaction_hash, error));
+				},
+			}
+		}
+	}
+
+	/// Schedule a minted transaction to be cleared after `ClearTxPeriod` blocks, or at the first
+	/// of the following `MAX_RESCHEDULE_BLOCKS` blocks with room left, clearing it right away
+	/// if there is none
+	fn settle(ledger_index: LedgerIndex, transaction_hash: TxHash) {
+		let clear_block_number =
+			<frame_system::Pallet<T>>::block_number() + T::ClearTxPeriod::get().into();
+		let scheduled = (0..MAX_RESCHEDULE_BLOCKS).any(|offset| {
+			<SettledTransactionDetails<T>>::try_append(
+				clear_block_number + offset.into(),
+				transaction_hash,
+			)
+			.is_ok()
+		});
+		if !scheduled {
+			Self::clear_transaction(transaction_hash);
+		}
+		<TransactionStatus<T>>::insert(transaction_hash, TxStatus::Settled);
+		Self::deposit_event(Event::Processed(ledger_index, transaction_hash));
+	}
+
+	/// Mint the asset registered for the currency of a payment to its destination, less the
+	/// bridge fee paid to the relayer that submitted it and to the treasury
+	/// The part of the fee that cannot be paid is left with the destination
+	pub fn mint(
+		transaction_hash: TxHash,
+		transaction: &TxData,
+		relayer: &T::AccountId,
+	) -> DispatchResult {
+		match *transaction {
+			TxData::Payment { amount, address, currency } => {
+				let asset_id =
+					Self::currency_registry(currency).ok_or(Error::<T>::UnknownCurrency)?;
+				let fee = Self::bridge_fee(asset_id, amount);
+				frame_support::storage::with_storage_layer(|| {
+					let paid = if fee > 0 {
+						Self::pay_fee(transaction_hash, asset_id, relayer, fee)
+					} else {
+						0
+					};
+					T::Assets::mint_into(asset_id, &address.into(), amount - paid)
+				})
+			},
+		}
+	}
+
+	/// Fee deducted from a payment of `amount`, the flat fee plus the basis points of the
+	/// `FeeSchedule`, at least the minimum fee of the asset and at most the whole payment
+	pub fn bridge_fee(asset_id: TokenId, amount: Balance) -> Balance {
+		let schedule = Self::fee_schedule();
+		let proportional = Permill::from_parts(schedule.basis_points.saturating_mul(100)) * amount;
+		schedule
+			.flat
+			.saturating_add(proportional)
+			.max(Self::minimum_fee(asset_id).unwrap_or_default())
+			.min(amount)
+	}
+
+	/// Mint the fee of a payment to the relayer and the treasury, returning the amount paid
+	/// The share of the relayer goes to the treasury if it cannot be minted to the relayer, and
+	/// the share of the treasury is not paid if it cannot be minted either, for instance when it
+	/// is below the minimum balance of the asset
+	fn pay_fee(
+		transaction_hash: TxHash,
+		asset_id: TokenId,
+		relayer: &T::AccountId,
+		fee: Balance,
+	) -> Balance {
+		let mut relayer_fee = Self::fee_schedule().relayer_share * fee;
+		if relayer_fee > 0 && T::Assets::mint_into(asset_id, relayer, relayer_fee).is_err() {
+			relayer_fee = 0;
+		}
+		let mut treasury_fee = fee - relayer_fee;
+		if relayer_fee > 0 {
+			RelayerEarnings::<T>::mutate(relayer, asset_id, |earnings| {
+				*earnings = earnings.saturating_add(relayer_fee)
+			});
+		}
+		if treasury_fee > 0 &&
+			T::Assets::mint_into(asset_id, &T::TreasuryAccount::get(), treasury_fee).is_err()
+		{
+			treasury_fee = 0;
+		}
+		if relayer_fee == 0 && treasury_fee == 0 {
+			return 0
+		}
+		Self::deposit_event(Event::FeePaid(
+			transaction_hash,
+			asset_id,
+			*relayer,
+			relayer_fee,
+			treasury_fee,
+		));
+		relayer_fee.saturating_add(treasury_fee)
+	}
+
+	/// Rate limit a payment would exceed if minted at block `n`, with the block its window ends
+	fn rate_limited(
+		n: T::BlockNumber,
+		transaction: &TxData,
+	) -> Option<(RateLimitScope, T::BlockNumber)> {
+		let (amount, address, currency) = match *transaction {
+			TxData::Payment { amount, address, currency } => (amount, address, currency),
+		};
+		if let Some(asset_id) = Self::currency_registry(currency) {
+			if let Some(limit) = Self::asset_rate_limit(asset_id) {
+				let window = Self::current_window(n, Self::asset_mint_window(asset_id), &limit);
+				if window.minted.saturating_add(amount) > limit.amount {
+					let end = window.start + limit.period.into();
+					return Some((RateLimitScope::Asset(asset_id), end))
+				}
+			}
+		}
+		if let Some(limit) = Self::destination_rate_limit() {
+			let window = Self::current_window(n, Self::destination_mint_window(address), &limit);
+			if window.minted.saturating_add(amount) > limit.amount {
+				let end = window.start + limit.period.into();
+				return Some((RateLimitScope::Destination(address), end))
 			}
 		}
-		DbWeight::get().reads_writes(reads, writes)
+		None
+	}
+
+	/// Rate limit a payment exceeds on its own, no window could ever mint it
+	fn oversized(transaction: &TxData) -> Option<RateLimitScope> {
+		let (amount, address, currency) = match *transaction {
+			TxData::Payment { amount, address, currency } => (amount, address, currency),
+		};
+		if let Some(asset_id) = Self::currency_registry(currency) {
+			if Self::asset_rate_limit(asset_id).map_or(false, |limit| amount > limit.amount) {
+				return Some(RateLimitScope::Asset(asset_id))
+			}
+		}
+		if Self::destination_rate_limit().map_or(false, |limit| amount > limit.amount) {
+			return Some(RateLimitScope::Destination(address))
+		}
+		None
+	}
+
+	/// Count a payment minted at block `n` in the windows of the rate limits it is subject to
+	fn record_mint(n: T::BlockNumber, transaction: &TxData) {
+		let (amount, address, currency) = match *transaction {
+			TxData::Payment { amount, address, currency } => (amount, address, currency),
+		};
+		if let Some(asset_id) = Self::currency_registry(currency) {
+			if let Some(limit) = Self::asset_rate_limit(asset_id) {
+				let mut window = Self::current_window(n, Self::asset_mint_window(asset_id), &limit);
+				window.minted = window.minted.saturating_add(amount);
+				AssetMintWindows::<T>::insert(asset_id, window);
+			}
+		}
+		if let Some(limit) = Self::destination_rate_limit() {
+			let mut window =
+				Self::current_window(n, Self::destination_mint_window(address), &limit);
+			window.minted = window.minted.saturating_add(amount);
+			DestinationMintWindows::<T>::insert(address, window);
+		}
+	}
+
+	/// Window of `limit` at block `n`, a new one starts once the previous one has ended
+	fn current_window(
+		n: T::BlockNumber,
+		window: Option<MintWindow<T::BlockNumber>>,
+		limit: &RateLimit,
+	) -> MintWindow<T::BlockNumber> {
+		match window {
+			Some(window) if n < window.start + limit.period.into() => window,
+			_ => MintWindow { start: n, minted: 0 },
+		}
+	}
+
+	/// Schedule a rate limited transaction at block `n`, or at the first of the following
+	/// `MAX_RESCHEDULE_BLOCKS` blocks with room left, `None` if there is none
+	fn defer(transaction_hash: TxHash, n: T::BlockNumber) -> Option<T::BlockNumber> {
+		let process_block_number = (0..MAX_RESCHEDULE_BLOCKS)
+			.map(|offset| n + offset.into())
+			.find(|block_number| {
+				ProcessTransaction::<T>::try_append(*block_number, transaction_hash).is_ok()
+			})?;
+		TransactionStatus::<T>::insert(transaction_hash, TxStatus::Deferred(process_block_number));
+		Some(process_block_number)
 	}
 
 	/// Prune settled transaction data from storage
 	/// if it was scheduled to do so at block `n`
 	pub fn clear_storages(n: T::BlockNumber) -> Weight {
-		let mut reads: u64 = 0;
-		let mut writes: u64 = 0;
-		reads += 1;
-		if <SettledTransactionDetails<T>>::contains_key(n) {
-			if let Some(tx_hashes) = <SettledTransactionDetails<T>>::take(n) {
-				writes += 1 + tx_hashes.len() as u64;
-				for tx_hash in tx_hashes {
-					<ProcessTransactionDetails<T>>::remove(tx_hash);
-				}
+		let tx_hashes = match <SettledTransactionDetails<T>>::take(n) {
+			None => return T::WeightInfo::clear_storages(0, T::MaxRelayers::get()),
+			Some(tx_hashes) => tx_hashes,
+		};
+		let weight = T::WeightInfo::clear_storages(tx_hashes.len() as u32, T::MaxRelayers::get());
+		for tx_hash in tx_hashes {
+			Self::clear_transaction(tx_hash);
+		}
+		weight
+	}
+
+	fn clear_transaction(transaction_hash: TxHash) {
+		<ProcessTransactionDetails<T>>::remove(transaction_hash);
+		let _ = <TransactionAttestations<T>>::clear_prefix(transaction_hash, u32::MAX, None);
+	}
+
+	/// Record the attestation of `relayer` and schedule the transaction once enough relayers
+	/// agree on the same data
+	/// Attestations of relayers removed or deactivated since are dropped, so only active
+	/// relayers, one attestation each, count towards the quorum and `MaxRelayers`
+	pub fn attest(
+		relayer: T::AccountId,
+		ledger_index: LedgerIndex,
+		transaction_hash: TxHash,
+		transaction: TxData,
+		timestamp: Timestamp,
+	) -> DispatchResult {
+		let attestation = (
+			ledger_index,
+			Transaction { transaction_hash, transaction: transaction.clone(), timestamp },
+		);
+		let mut matching: u32 = 1;
+		let mut disputed = false;
+		let mut attestations = 0;
+		let mut dropped = Vec::new();
+		for (other_relayer, other) in <TransactionAttestations<T>>::iter_prefix(transaction_hash) {
+			if !Self::get_relayer(other_relayer).unwrap_or(false) {
+				dropped.push(other_relayer);
+				continue
+			}
+			attestations += 1;
+			if other == attestation {
+				matching += 1;
+			} else {
+				disputed = true;
 			}
 		}
-		DbWeight::get().reads_writes(reads, writes)
+		ensure!(attestations < T::MaxRelayers::get(), Error::<T>::TooManyAttestations);
+		for other_relayer in dropped {
+			<TransactionAttestations<T>>::remove(transaction_hash, other_relayer);
+		}
+		<TransactionAttestations<T>>::insert(transaction_hash, relayer, attestation);
+
+		if disputed {
+			Self::deposit_event(Event::AttestationMismatch(
+				ledger_index,
+				transaction_hash,
+				relayer,
+			));
+		}
+		if matching >= Self::relayer_threshold() {
+			Self::add_to_relay(relayer, ledger_index, transaction_hash, transaction, timestamp)
+		} else {
+			Self::deposit_event(Event::TransactionAttested(
+				ledger_index,
+				transaction_hash,
+				relayer,
+			));
+			Ok(())
+		}
 	}
 
 	pub fn add_to_relay(
@@ -287,18 +1712,198 @@ impl<T: Config> Pallet<T> {
 		transaction: TxData,
 		timestamp: Timestamp,
 	) -> DispatchResult {
+		let process_at = Self::add_to_process(transaction_hash, &transaction)?;
+		AdmittedTransactions::<T>::remove(transaction_hash);
 		let val = Transaction { transaction_hash, transaction, timestamp };
 		<ProcessTransactionDetails<T>>::insert(transaction_hash, (ledger_index, val, relayer));
-
-		Self::add_to_process(transaction_hash)?;
-		Self::deposit_event(Event::TransactionAdded(ledger_index, transaction_hash));
+		Self::deposit_event(Event::TransactionAdded(ledger_index, transaction_hash, process_at));
 		Ok(())
 	}
 
-	pub fn add_to_process(transaction_hash: TxHash) -> DispatchResult {
-		let process_block_number =
-			<frame_system::Pallet<T>>::block_number() + T::ChallengePeriod::get().into();
-		ProcessTransaction::<T>::append(process_block_number, transaction_hash);
-		Ok(())
+	/// Schedule a transaction at the end of the challenge period of its amount tier
+	pub fn add_to_process(
+		transaction_hash: TxHash,
+		transaction: &TxData,
+	) -> Result<ProcessAt<T::BlockNumber>, DispatchError> {
+		let tier_period = Self::tier_challenge_period(transaction);
+		let process_at = match T::WallClockChallengePeriod::get() {
+			Some(period) => {
+				let now = Self::unix_now();
+				let process_time = now.saturating_add(tier_period.map_or(period, u64::from));
+				TimedProcessTransaction::<T>::try_append(process_time, transaction_hash)
+					.map_err(|_| Error::<T>::TooManyTransactions)?;
+				if Self::timed_process_cursor().is_none() {
+					TimedProcessCursor::<T>::put(now);
+				}
+				ProcessAt::Time(process_time)
+			},
+			None => {
+				let period = tier_period.unwrap_or_else(T::ChallengePeriod::get);
+				let process_block_number =
+					<frame_system::Pallet<T>>::block_number() + period.into();
+				ProcessTransaction::<T>::try_append(process_block_number, transaction_hash)
+					.map_err(|_| Error::<T>::TooManyTransactions)?;
+				ProcessAt::Block(process_block_number)
+			},
+		};
+		TransactionStatus::<T>::insert(transaction_hash, TxStatus::Pending(process_at));
+		Ok(process_at)
+	}
+
+	/// Challenge period of the highest tier of `ChallengePeriodTiers` reached by the amount
+	fn tier_challenge_period(transaction: &TxData) -> Option<u32> {
+		let amount = match transaction {
+			TxData::Payment { amount, .. } => *amount,
+		};
+		Self::challenge_period_tiers()
+			.iter()
+			.rev()
+			.find(|(threshold, _)| *threshold <= amount)
+			.map(|(_, period)| *period)
+	}
+
+	/// Current Unix time in seconds
+	pub fn unix_now() -> u64 {
+		T::UnixTime::now().as_secs()
+	}
+
+	/// Whether a transaction scheduled at `process_at` may be processed at block `n`
+	fn is_due(process_at: ProcessAt<T::BlockNumber>, n: T::BlockNumber) -> bool {
+		match process_at {
+			ProcessAt::Block(block_number) => block_number <= n,
+			ProcessAt::Time(process_time) => process_time <= Self::unix_now(),
+		}
+	}
+
+	/// Schedule a transaction whose challenge failed at its original time, or after a new
+	/// challenge period if that time has passed
+	fn release(transaction_hash: TxHash, process_at: ProcessAt<T::BlockNumber>) {
+		let now = <frame_system::Pallet<T>>::block_number();
+		if !Self::is_due(process_at, now) {
+			TransactionStatus::<T>::insert(transaction_hash, TxStatus::Pending(process_at));
+			return
+		}
+		let transaction = Self::process_transaction_details(transaction_hash)
+			.map(|(_, tx, _)| tx.transaction)
+			.unwrap_or_default();
+		if Self::add_to_process(transaction_hash, &transaction).is_err() {
+			// No room left at the new process block, process it with the carried over ones
+			Self::carry_over(transaction_hash);
+			TransactionStatus::<T>::insert(
+				transaction_hash,
+				TxStatus::Pending(ProcessAt::Block(now)),
+			);
+		}
 	}
+
+	/// Drop a transaction proven invalid, its hash may be submitted again
+	fn reject(transaction_hash: TxHash, process_at: ProcessAt<T::BlockNumber>) {
+		let retain = |queue: &mut Option<TxHashesOf<T>>| {
+			if let Some(tx_hashes) = queue {
+				tx_hashes.retain(|tx_hash| *tx_hash != transaction_hash);
+				if tx_hashes.is_empty() {
+					*queue = None;
+				}
+			}
+		};
+		match process_at {
+			ProcessAt::Block(block_number) =>
+				ProcessTransaction::<T>::mutate_exists(block_number, retain),
+			ProcessAt::Time(process_time) =>
+				TimedProcessTransaction::<T>::mutate_exists(process_time, retain),
+		}
+		let _ = TransactionAttestations::<T>::clear_prefix(transaction_hash, u32::MAX, None);
+		TransactionStatus::<T>::insert(transaction_hash, TxStatus::Rejected);
+		if let Some((ledger_index, _, _)) = ProcessTransactionDetails::<T>::take(transaction_hash) {
+			Self::deposit_event(Event::<T>::TransactionRejected(ledger_index, transaction_hash));
+		}
+	}
+
+	/// Resolve the challenges expiring at block `n` according to `ChallengeExpiryPolicy`
+	pub fn expire_challenges(n: T::BlockNumber) -> Weight {
+		let tx_hashes = match <ChallengeExpiries<T>>::take(n) {
+			None => return T::WeightInfo::expire_challenges(0, T::MaxRelayers::get()),
+			Some(tx_hashes) => tx_hashes,
+		};
+		let weight =
+			T::WeightInfo::expire_challenges(tx_hashes.len() as u32, T::MaxRelayers::get());
+		let policy = T::ChallengeExpiryPolicy::get();
+		for transaction_hash in tx_hashes {
+			let challenge = match <ChallengeTransactionList<T>>::get(transaction_hash) {
+				// Skip challenges resolved by `ApproveOrigin` in the meantime
+				Some(challenge) if challenge.expires_at == n => challenge,
+				_ => continue,
+			};
+			let process_at = match Self::challenged_process_at(transaction_hash) {
+				Ok(process_at) => process_at,
+				Err(_) => continue,
+			};
+			<ChallengeTransactionList<T>>::remove(transaction_hash);
+			let unreserved = T::Currency::unreserve(&challenge.challenger, challenge.bond);
+			match policy {
+				ChallengeExpiry::Reject => Self::reject(transaction_hash, process_at),
+				ChallengeExpiry::Release => Self::release(transaction_hash, process_at),
+			}
+			Self::deposit_event(Event::ChallengeExpired(
+				transaction_hash,
+				challenge.challenger,
+				challenge.bond.saturating_sub(unreserved),
+				policy,
+			));
+		}
+		weight
+	}
+
+	/// When a challenged transaction was scheduled to be processed
+	fn challenged_process_at(
+		transaction_hash: TxHash,
+	) -> Result<ProcessAt<T::BlockNumber>, DispatchError> {
+		match Self::transaction_status(transaction_hash) {
+			Some(TxStatus::Challenged(process_at)) => Ok(process_at),
+			_ => Err(Error::<T>::ChallengeNotFound.into()),
+		}
+	}
+
+	/// Transactions waiting for their challenge period to end, with when they are processed
+	/// Only the schedules and the carry-over queue are walked, not the settled transactions
+	pub fn pending_transactions() -> Vec<(TxHash, ProcessAt<T::BlockNumber>)> {
+		let (head, tail) = Self::carry_over_cursor();
+		(head..tail)
+			.filter_map(<CarryOverQueue<T>>::get)
+			.chain(<ProcessTransaction<T>>::iter_values().flatten())
+			.chain(<TimedProcessTransaction<T>>::iter_values().flatten())
+			.filter_map(|transaction_hash| match Self::transaction_status(transaction_hash) {
+				Some(TxStatus::Pending(process_at)) => Some((transaction_hash, process_at)),
+				_ => None,
+			})
+			.collect()
+	}
+
+	/// Challenges waiting for a resolution
+	pub fn open_challenges() -> Vec<(TxHash, Challenge<T::AccountId, T::BlockNumber, Vec<u8>>)> {
+		<ChallengeTransactionList<T>>::iter()
+			.map(|(transaction_hash, challenge)| {
+				let Challenge { challenger, bond, reason, evidence, expires_at } = challenge;
+				let evidence = evidence.map(|evidence| evidence.into_inner());
+				(transaction_hash, Challenge { challenger, bond, reason, evidence, expires_at })
+			})
+			.collect()
+	}
+
+	/// Whether `relayer` already attested to a transaction, which it can not submit again
+	pub fn attested(transaction_hash: TxHash, relayer: &T::AccountId) -> bool {
+		TransactionAttestations::<T>::contains_key(transaction_hash, relayer)
+	}
+
+	/// Relayers allowed to submit transactions
+	pub fn relayers() -> Vec<T::AccountId> {
+		<Relayer<T>>::iter()
+			.filter_map(|(relayer, active)| if active { Some(relayer) } else { None })
+			.collect()
+	}
+}
+
+/// Relayer keys are generated with the session keys, so dev chains get one from the dev seed
+impl<T: Config> sp_runtime::BoundToRuntimeAppPublic for Pallet<T> {
+	type Public = crypto::Public;
 }
@@ -0,0 +1,180 @@
+//! Storage migrations of the bridge pallet
+
+use super::*;
+use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+use sp_core::H160;
+use sp_runtime::traits::AtLeast32BitUnsigned;
+use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData};
+
+/// Storage version 1 adds the currency of payments, bounds the schedules, records challenges
+/// with their bond and tracks the status of every transaction
+pub mod v1 {
+	use super::*;
+
+	/// Payment of storage version 0, always minted into `Config::AssetId`
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub enum OldTxData {
+		Payment { amount: Balance, address: H160 },
+	}
+
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct OldTransaction {
+		pub transaction_hash: TxHash,
+		pub transaction: OldTxData,
+		pub timestamp: u64,
+	}
+
+	pub type OldTransactionDetails<T> =
+		(LedgerIndex, OldTransaction, <T as frame_system::Config>::AccountId);
+
+	/// Migrate from storage version 0
+	///
+	/// `LegacyCurrency` is registered for `Config::AssetId` and becomes the currency of the
+	/// payments already submitted. Scheduled transactions keep their block, or move to the first
+	/// following block with room left. Open challenges get no bond and a new resolution period,
+	/// challenges of transactions already removed are dropped as accepted and the ones of minted
+	/// transactions are dropped. Transactions pruned before the migration get no status.
+	pub struct MigrateToV1<T, LegacyCurrency>(PhantomData<(T, LegacyCurrency)>);
+
+	impl<T: Config, LegacyCurrency: Get<ExternalCurrency>> OnRuntimeUpgrade
+		for MigrateToV1<T, LegacyCurrency>
+	{
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 1 {
+				return T::DbWeight::get().reads(1)
+			}
+			let now = <frame_system::Pallet<T>>::block_number();
+			let currency = LegacyCurrency::get();
+			let (mut reads, mut writes) = (1u64, 2u64);
+
+			if !CurrencyRegistry::<T>::contains_key(currency) {
+				CurrencyRegistry::<T>::insert(currency, T::AssetId::get());
+			}
+
+			ProcessTransactionDetails::<T>::translate::<OldTransactionDetails<T>, _>(
+				|_, (ledger_index, old, relayer)| {
+					reads += 1;
+					writes += 1;
+					let OldTxData::Payment { amount, address } = old.transaction;
+					let transaction = Transaction {
+						transaction_hash: old.transaction_hash,
+						transaction: TxData::Payment { amount, address, currency },
+						timestamp: old.timestamp,
+					};
+					Some((ledger_index, transaction, relayer))
+				},
+			);
+
+			// Taken out before they are scheduled again within `MaxTransactionsPerBlock`
+			let mut scheduled = Vec::new();
+			ProcessTransaction::<T>::translate::<Vec<TxHash>, _>(|block_number, tx_hashes| {
+				reads += 1;
+				writes += 1;
+				scheduled.extend(tx_hashes.into_iter().map(|tx_hash| (block_number, tx_hash)));
+				None
+			});
+			let mut settled = Vec::new();
+			SettledTransactionDetails::<T>::translate::<Vec<TxHash>, _>(
+				|block_number, tx_hashes| {
+					reads += 1;
+					writes += 1;
+					settled.extend(tx_hashes.into_iter().map(|tx_hash| (block_number, tx_hash)));
+					None
+				},
+			);
+			let total = (scheduled.len() + settled.len()) as u32;
+			let minted: BTreeSet<TxHash> = settled.iter().map(|(_, tx_hash)| *tx_hash).collect();
+
+			let mut challenged = BTreeSet::new();
+			let expires_at = now + T::ChallengeResolutionPeriod::get().into();
+			ChallengeTransactionList::<T>::translate::<T::AccountId, _>(
+				|transaction_hash, challenger| {
+					reads += 2;
+					writes += 1;
+					// Challenged once minted, too late to be resolved
+					if minted.contains(&transaction_hash) {
+						return None
+					}
+					if !ProcessTransactionDetails::<T>::contains_key(transaction_hash) {
+						TransactionStatus::<T>::insert(transaction_hash, TxStatus::Rejected);
+						return None
+					}
+					let expires_at = append_from(expires_at, total, |block_number| {
+						ChallengeExpiries::<T>::try_append(block_number, transaction_hash)
+					})?;
+					challenged.insert(transaction_hash);
+					writes += 2;
+					Some(Challenge {
+						challenger,
+						bond: 0,
+						reason: ChallengeReason::Other,
+						evidence: None,
+						expires_at,
+					})
+				},
+			);
+
+			for (block_number, transaction_hash) in scheduled {
+				let block_number = match append_from(block_number, total, |block_number| {
+					ProcessTransaction::<T>::try_append(block_number, transaction_hash)
+				}) {
+					Some(block_number) => block_number,
+					None => continue,
+				};
+				let process_at = ProcessAt::Block(block_number);
+				let status = if challenged.remove(&transaction_hash) {
+					TxStatus::Challenged(process_at)
+				} else {
+					TxStatus::Pending(process_at)
+				};
+				TransactionStatus::<T>::insert(transaction_hash, status);
+				writes += 2;
+			}
+			// Challenged after their block, they get a new challenge period once released
+			for transaction_hash in challenged {
+				TransactionStatus::<T>::insert(
+					transaction_hash,
+					TxStatus::Challenged(ProcessAt::Block(now)),
+				);
+				writes += 1;
+			}
+			for (block_number, transaction_hash) in settled {
+				if append_from(block_number, total, |block_number| {
+					SettledTransactionDetails::<T>::try_append(block_number, transaction_hash)
+				})
+				.is_some()
+				{
+					TransactionStatus::<T>::insert(transaction_hash, TxStatus::Settled);
+					writes += 2;
+				}
+			}
+
+			let relayers = Relayer::<T>::iter_keys().count() as u64;
+			RelayerCount::<T>::put(relayers as u32);
+			reads += relayers;
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target: "runtime::bridge", "migrated bridge storage to version 1");
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(StorageVersion::get::<Pallet<T>>() == 1, "bridge storage version not updated");
+			Ok(())
+		}
+	}
+
+	/// Append to the first queue from `block_number` with room left
+	/// `total` entries are migrated, so one of as many following blocks has room left unless
+	/// `MaxTransactionsPerBlock` is 0
+	fn append_from<BlockNumber: AtLeast32BitUnsigned + Copy>(
+		block_number: BlockNumber,
+		total: u32,
+		mut try_append: impl FnMut(BlockNumber) -> Result<(), ()>,
+	) -> Option<BlockNumber> {
+		(0..=total)
+			.map(|offset| block_number + offset.into())
+			.find(|block_number| try_append(*block_number).is_ok())
+	}
+}
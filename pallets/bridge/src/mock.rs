@@ -1,16 +1,22 @@
 use crate as pallet_bridge;
+use crate::helpers::{ChallengeExpiry, ExternalCurrency, TxData};
 
 use frame_support::{
-	construct_runtime, parameter_types,
-	traits::{ConstU16, ConstU64},
+	assert_ok, construct_runtime, parameter_types,
+	traits::{fungibles::Inspect, ConstU16, ConstU64, GenesisBuild},
+	PalletId,
 };
 use frame_system as system;
 use frame_system::{limits, EnsureRoot};
-use primitives::types::{AccountId, Balance, BlockNumber, TokenId};
+use primitives::{
+	bridge::{Address, TxHash},
+	types::{AccountId, Balance, BlockNumber, Signature, TokenId},
+};
 use sp_core::{H160, H256};
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	testing::{Header, TestXt},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup, Verify},
+	Perbill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -26,7 +32,7 @@ construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Assets: pallet_assets::{Pallet, Storage, Config<T>, Event<T>},
-		Bridge: pallet_bridge::{Pallet, Call, Storage, Event<T>},
+		Bridge: pallet_bridge::{Pallet, Call, Config, Storage, Event<T>},
 		TimestampPallet: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 	}
 );
@@ -125,21 +131,124 @@ pub const DAYS: BlockNumber = HOURS * 24;
 parameter_types! {
 	pub const TxChallengePeriod: u32 = 10 * MINUTES;
 	pub const ClearTxPeriod: u32 = 10 * DAYS;
+	pub static MaxProcessPerBlock: u32 = 10;
+	pub static MaxTransactionsPerBlock: u32 = 100;
 	pub const TokenAssetId: TokenId = 2;
+	pub const ChallengeBond: Balance = 100;
+	pub const ChallengeReward: Balance = 50;
+	pub const MaxEvidenceLength: u32 = 64;
+	pub const ChallengeResolutionPeriod: u32 = 50;
+	pub static ChallengeExpiryPolicy: ChallengeExpiry = ChallengeExpiry::Release;
+	pub const BridgePalletId: PalletId = PalletId(*b"py/bridg");
+	pub static MaxRelayers: u32 = 10;
+	pub const RelayerMinStake: Balance = 1_000;
+	pub const MaxTimestampDrift: u64 = 60;
+	pub const MaxTransactionAge: u64 = 3_600;
+	pub static WallClockChallengePeriod: Option<u64> = None;
+	pub static MaxTimedScanPerBlock: u32 = 1_000;
+	pub const MaxChallengePeriodTiers: u32 = 4;
+	pub const RelayerUnbondingPeriod: u32 = 10;
+	pub const RelayerSlash: Perbill = Perbill::from_percent(50);
+	pub const ReserveTolerance: Perbill = Perbill::from_percent(1);
+	pub TreasuryAccount: AccountId = create_account(b"6490B68F1116BFE87DD9");
+	pub const OffchainLedgersPerRun: u32 = 2;
+	pub const OffchainTransactionsPerRun: u32 = 10;
+	/// Whether the offchain worker manages to sign the transactions it submits
+	pub static SignTransactions: bool = true;
 }
 
 impl pallet_bridge::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type ChallengePeriod = TxChallengePeriod;
 	type ClearTxPeriod = ClearTxPeriod;
+	type MaxProcessPerBlock = MaxProcessPerBlock;
+	type MaxTransactionsPerBlock = MaxTransactionsPerBlock;
 	type UnixTime = TimestampPallet;
+	type MaxTimestampDrift = MaxTimestampDrift;
+	type MaxTransactionAge = MaxTransactionAge;
+	type MaxChallengePeriodTiers = MaxChallengePeriodTiers;
+	type WallClockChallengePeriod = WallClockChallengePeriod;
+	type MaxTimedScanPerBlock = MaxTimedScanPerBlock;
 	type ApproveOrigin = EnsureRoot<Self::AccountId>;
+	type PauseOrigin = EnsureRoot<Self::AccountId>;
 	type Assets = Assets;
 	type AssetId = TokenAssetId;
+	type Currency = Balances;
+	type ChallengeBond = ChallengeBond;
+	type ChallengeReward = ChallengeReward;
+	type MaxEvidenceLength = MaxEvidenceLength;
+	type ChallengeResolutionPeriod = ChallengeResolutionPeriod;
+	type ChallengeExpiryPolicy = ChallengeExpiryPolicy;
+	type WeightInfo = ();
+	type PalletId = BridgePalletId;
+	type MaxRelayers = MaxRelayers;
+	type RelayerMinStake = RelayerMinStake;
+	type RelayerUnbondingPeriod = RelayerUnbondingPeriod;
+	type RelayerSlash = RelayerSlash;
+	type ReserveTolerance = ReserveTolerance;
+	type TreasuryAccount = TreasuryAccount;
+	type AuthorityId = crate::crypto::RelayerAuthId;
+	type OffchainLedgersPerRun = OffchainLedgersPerRun;
+	type OffchainTransactionsPerRun = OffchainTransactionsPerRun;
 }
+
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: Self::Public,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		SignTransactions::get().then_some((call, (nonce, ())))
+	}
+}
+
+/// Free balance given at genesis to the accounts used in tests
+pub const INITIAL_BALANCE: Balance = 1_000_000;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: [
+			b"6490B68F1116BFE87DDC",
+			b"6490B68F1116BFE87DDD",
+			b"6490B68F1116BFE87DDE",
+			b"6490B68F1116BFE87DD1",
+			b"6490B68F1116BFE87DD2",
+			b"6490B68F1116BFE87DD3",
+		]
+		.iter()
+		.map(|address| (create_account(*address), INITIAL_BALANCE))
+		.collect(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_bridge::GenesisConfig::<Test> {
+		relayers: vec![],
+		relayer_threshold: 1,
+		currencies: vec![(jur_currency(), TokenAssetId::get())],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
 
 	let mut ext = sp_io::TestExternalities::new(t);
 	ext.execute_with(|| {
@@ -160,10 +269,26 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 			false,
 		)
 		.unwrap();
+		TimestampPallet::set_timestamp(GENESIS_TIME * 1_000);
 	});
 	ext
 }
 
+/// Unix time in seconds at the start of each test
+pub const GENESIS_TIME: u64 = 1_300;
+
+/// Currency of the external ledger bridged to the JUR asset
+pub fn jur_currency() -> ExternalCurrency {
+	currency(b"JUR")
+}
+
+/// Helper function to build an external currency from a standard three letter code
+pub fn currency(code: &[u8; 3]) -> ExternalCurrency {
+	let mut currency_code = [0u8; 20];
+	currency_code[12..15].copy_from_slice(code);
+	ExternalCurrency { code: currency_code, issuer: H160::from_low_u64_be(1) }
+}
+
 /// Helper function to create an AccountId from  a slice
 pub fn create_account(address: &[u8]) -> AccountId {
 	AccountId::from(H160::from_slice(address))
@@ -172,3 +297,24 @@ pub fn create_account(address: &[u8]) -> AccountId {
 pub fn token(d: u128) -> u128 {
 	d.saturating_mul(10_u128.pow(6))
 }
+
+/// Relayer of the tests, funded at genesis
+pub fn relayer() -> AccountId {
+	create_account(b"6490B68F1116BFE87DDD")
+}
+
+/// Helper function to submit a payment of `amount` JUR to `destination` from `relayer()`
+pub fn submit_payment(transaction_hash: TxHash, amount: Balance, destination: Address) {
+	assert_ok!(Bridge::submit_transaction(
+		RuntimeOrigin::signed(relayer()),
+		1,
+		transaction_hash,
+		TxData::Payment { amount, address: destination, currency: jur_currency() },
+		1234
+	));
+}
+
+/// Helper function to get the token balance of an account
+pub fn token_balance_of(account: impl Into<AccountId>) -> Balance {
+	Assets::balance(TokenAssetId::get(), &account.into())
+}
@@ -0,0 +1,696 @@
+//! Offchain worker relaying payments to the door account on the external ledger, and watching
+//! the transactions other relayers submit
+//!
+//! The worker runs on nodes configured in the `PERSISTENT` offchain local storage, for instance
+//! with the `offchain_localStorageSet` RPC, both values as raw UTF-8:
+//! - `LEDGER_ENDPOINT_KEY`: JSON-RPC URL of a node of the external ledger
+//! - `DOOR_ACCOUNT_KEY`: classic address of the door account
+//!
+//! It scans validated ledgers from the one after `LAST_LEDGER_KEY` and submits their payments
+//! to the door account with a relayer key of type `crypto::KEY_TYPE` from the node keystore.
+//!
+//! Nodes with any value under `WATCHER_KEY` also look up the pending transactions on the
+//! external ledger, and challenge the ones that do not match it with a key of the same type.
+
+use super::*;
+use frame_system::offchain::{AppCrypto, SendSignedTransaction, Signer};
+use lite_json::JsonValue;
+use sp_core::offchain::StorageKind;
+use sp_runtime::{
+	offchain::{
+		http,
+		storage::StorageValueRef,
+		storage_lock::{BlockAndTime, StorageLock},
+		Duration,
+	},
+	traits::{IdentifyAccount, One},
+	RuntimeAppPublic,
+};
+
+/// JSON-RPC URL of the external ledger
+pub const LEDGER_ENDPOINT_KEY: &[u8] = b"bridge::ledger-endpoint";
+/// Classic address of the door account
+pub const DOOR_ACCOUNT_KEY: &[u8] = b"bridge::door-account";
+/// Last ledger scanned by the relayer
+pub const LAST_LEDGER_KEY: &[u8] = b"bridge::last-ledger";
+/// Verify pending transactions and challenge the mismatched ones
+pub const WATCHER_KEY: &[u8] = b"bridge::watcher";
+const RELAY_LOCK_KEY: &[u8] = b"bridge::relay-lock";
+/// Payment whose submission failed and the number of attempts
+const RELAY_FAILURES_KEY: &[u8] = b"bridge::relay-failures";
+const WATCH_LOCK_KEY: &[u8] = b"bridge::watch-lock";
+/// Pending transactions already verified by the watcher, pruned once they leave `Pending`
+pub const WATCHED_KEY: &[u8] = b"bridge::watched";
+
+const LOG_TARGET: &str = "runtime::bridge";
+const HTTP_TIMEOUT_MS: u64 = 5_000;
+/// The relay lock is held at most for this long, or until the next block
+const LOCK_TIMEOUT_MS: u64 = 60_000;
+/// Failed submissions of a payment after which the relayer skips it
+const MAX_SUBMIT_ATTEMPTS: u32 = 5;
+/// Seconds from the Unix epoch to the epoch of ledger close times, 2000-01-01
+const LEDGER_EPOCH: u64 = 946_684_800;
+/// Decimals of the amounts relayed, the precision of native drops
+const AMOUNT_DECIMALS: u32 = 6;
+const ADDRESS_ALPHABET: &[u8; 58] = b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz";
+
+#[derive(PartialEq, Eq, Clone, Copy, RuntimeDebug)]
+pub enum OffchainError {
+	/// No relayer key of the keystore is a registered relayer
+	NoRelayerKey,
+	/// No key of the keystore to sign challenges with
+	NoWatcherKey,
+	InvalidEndpoint,
+	Http,
+	InvalidResponse,
+	/// The ledger is not validated yet
+	NotValidated,
+	SubmitFailed,
+}
+
+/// Payment to the door account on a validated ledger
+#[derive(PartialEq, Eq, Clone, RuntimeDebug)]
+pub struct LedgerPayment {
+	pub transaction_hash: TxHash,
+	pub transaction: TxData,
+	pub timestamp: Timestamp,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Relay the payments of the validated ledgers not scanned yet
+	/// Does nothing on nodes without a configured endpoint and door account
+	/// A payment whose submission keeps failing is skipped after `MAX_SUBMIT_ATTEMPTS` runs
+	pub fn relay_ledgers(n: T::BlockNumber) -> Result<(), OffchainError> {
+		let (endpoint, door_account) =
+			match (local_storage(LEDGER_ENDPOINT_KEY), local_storage(DOOR_ACCOUNT_KEY)) {
+				(Some(endpoint), Some(door_account)) => (endpoint, door_account),
+				_ => return Ok(()),
+			};
+		if Self::bridge_mode().inbound_submission_paused {
+			return Ok(())
+		}
+		let public = Self::relayer_key().ok_or(OffchainError::NoRelayerKey)?;
+
+		let mut lock =
+			StorageLock::<BlockAndTime<frame_system::Pallet<T>>>::with_block_and_time_deadline(
+				RELAY_LOCK_KEY,
+				1,
+				Duration::from_millis(LOCK_TIMEOUT_MS),
+			);
+		let _guard = match lock.try_lock() {
+			Ok(guard) => guard,
+			Err(_) => return Ok(()),
+		};
+
+		let validated = validated_ledger_index(&endpoint)?;
+		let last_ledger = StorageValueRef::persistent(LAST_LEDGER_KEY);
+		let from = match last_ledger.get::<LedgerIndex>() {
+			Ok(Some(last)) => last.saturating_add(1),
+			_ => validated,
+		};
+		let to = validated
+			.min(from.saturating_add(T::OffchainLedgersPerRun::get().saturating_sub(1).into()));
+		for ledger_index in from..=to {
+			for payment in ledger_payments(&endpoint, ledger_index, &door_account)? {
+				let transaction_hash = payment.transaction_hash;
+				if let Err(error) = Self::relay_payment(&public, ledger_index, payment) {
+					if !give_up(transaction_hash) {
+						return Err(error)
+					}
+					log::error!(
+						target: LOG_TARGET,
+						"skipping {:?} of ledger {} after {} failed submissions",
+						transaction_hash,
+						ledger_index,
+						MAX_SUBMIT_ATTEMPTS
+					);
+				}
+			}
+			last_ledger.set(&ledger_index);
+		}
+		log::debug!(target: LOG_TARGET, "relayed ledgers {}..={} at block {:?}", from, to, n);
+		Ok(())
+	}
+
+	/// Submit a payment, unless this relayer already attested it
+	fn relay_payment(
+		public: &T::Public,
+		ledger_index: LedgerIndex,
+		payment: LedgerPayment,
+	) -> Result<(), OffchainError> {
+		let LedgerPayment { transaction_hash, transaction, timestamp } = payment;
+		let TxData::Payment { currency, .. } = &transaction;
+		let relayer = public.clone().into_account();
+		if !matches!(Self::transaction_status(transaction_hash), None | Some(TxStatus::Rejected)) ||
+			Self::attested(transaction_hash, &relayer) ||
+			!CurrencyRegistry::<T>::contains_key(currency)
+		{
+			return Ok(())
+		}
+		let result = Signer::<T, T::AuthorityId>::any_account()
+			.with_filter(vec![public.clone()])
+			.send_signed_transaction(|_| Call::submit_transaction {
+				ledger_index,
+				transaction_hash,
+				transaction: transaction.clone(),
+				timestamp,
+			});
+		match result {
+			Some((_, Ok(()))) => Ok(()),
+			_ => Err(OffchainError::SubmitFailed),
+		}
+	}
+
+	/// Compare the pending transactions with the external ledger and challenge the mismatched ones
+	/// Does nothing on nodes without a configured endpoint, door account and watcher
+	pub fn watch_transactions(n: T::BlockNumber) -> Result<(), OffchainError> {
+		let (endpoint, door_account) = match (
+			local_storage(LEDGER_ENDPOINT_KEY),
+			local_storage(DOOR_ACCOUNT_KEY),
+			local_storage(WATCHER_KEY),
+		) {
+			(Some(endpoint), Some(door_account), Some(_)) => (endpoint, door_account),
+			_ => return Ok(()),
+		};
+		if Self::bridge_mode().challenges_paused {
+			return Ok(())
+		}
+		if !Signer::<T, T::AuthorityId>::any_account().can_sign() {
+			return Err(OffchainError::NoWatcherKey)
+		}
+
+		let mut lock =
+			StorageLock::<BlockAndTime<frame_system::Pallet<T>>>::with_block_and_time_deadline(
+				WATCH_LOCK_KEY,
+				1,
+				Duration::from_millis(LOCK_TIMEOUT_MS),
+			);
+		let _guard = match lock.try_lock() {
+			Ok(guard) => guard,
+			Err(_) => return Ok(()),
+		};
+
+		// A challenge is included at the earliest in the next block
+		let next_block = n.saturating_add(One::one());
+		let watched_ref = StorageValueRef::persistent(WATCHED_KEY);
+		let mut watched = watched_ref.get::<Vec<TxHash>>().ok().flatten().unwrap_or_default();
+		watched.retain(|transaction_hash| {
+			matches!(Self::transaction_status(transaction_hash), Some(TxStatus::Pending(_)))
+		});
+		let mut verified = 0;
+		let mut result = Ok(());
+		for (transaction_hash, process_at) in Self::pending_transactions() {
+			if verified >= T::OffchainTransactionsPerRun::get() {
+				break
+			}
+			if Self::is_due(process_at, next_block) || watched.contains(&transaction_hash) {
+				continue
+			}
+			let (ledger_index, tx, _) = match Self::process_transaction_details(transaction_hash) {
+				Some(details) => details,
+				None => continue,
+			};
+			verified += 1;
+			match verify_transaction(&endpoint, &door_account, ledger_index, &tx) {
+				Ok(Some((reason, evidence))) =>
+					if let Err(error) = Self::challenge(transaction_hash, reason, evidence) {
+						result = Err(error);
+						break
+					},
+				Ok(None) => {},
+				// Looked up again once validated
+				Err(OffchainError::NotValidated) => continue,
+				Err(error) => {
+					result = Err(error);
+					break
+				},
+			}
+			watched.push(transaction_hash);
+		}
+		watched_ref.set(&watched);
+		result
+	}
+
+	fn challenge(
+		transaction_hash: TxHash,
+		reason: ChallengeReason,
+		mut evidence: Vec<u8>,
+	) -> Result<(), OffchainError> {
+		log::info!(
+			target: LOG_TARGET,
+			"challenging {:?}: {:?} {}",
+			transaction_hash,
+			reason,
+			sp_std::str::from_utf8(&evidence).unwrap_or_default()
+		);
+		evidence.truncate(T::MaxEvidenceLength::get() as usize);
+		let result = Signer::<T, T::AuthorityId>::any_account().send_signed_transaction(|_| {
+			Call::submit_challenge { transaction_hash, reason, evidence: Some(evidence.clone()) }
+		});
+		match result {
+			Some((_, Ok(()))) => Ok(()),
+			_ => Err(OffchainError::SubmitFailed),
+		}
+	}
+
+	/// First key of the keystore belonging to an active relayer
+	pub fn relayer_key() -> Option<T::Public> {
+		<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+			.into_iter()
+			.map(|key| {
+				let generic: <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic =
+					key.into();
+				generic.into()
+			})
+			.find(|public: &T::Public| {
+				Self::get_relayer(public.clone().into_account()).unwrap_or(false)
+			})
+	}
+}
+
+/// Count a failed submission of a payment, whether it failed `MAX_SUBMIT_ATTEMPTS` times
+fn give_up(transaction_hash: TxHash) -> bool {
+	let failures = StorageValueRef::persistent(RELAY_FAILURES_KEY);
+	let attempts = match failures.get::<(TxHash, u32)>() {
+		Ok(Some((failed, attempts))) if failed == transaction_hash => attempts + 1,
+		_ => 1,
+	};
+	if attempts >= MAX_SUBMIT_ATTEMPTS {
+		failures.clear();
+		true
+	} else {
+		failures.set(&(transaction_hash, attempts));
+		false
+	}
+}
+
+fn local_storage(key: &[u8]) -> Option<Vec<u8>> {
+	sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, key)
+		.filter(|value| !value.is_empty())
+}
+
+/// Call a method of the external ledger JSON-RPC API and return its result
+fn rpc_call(endpoint: &[u8], body: Vec<u8>) -> Result<JsonValue, OffchainError> {
+	let url = sp_std::str::from_utf8(endpoint).map_err(|_| OffchainError::InvalidEndpoint)?;
+	let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(HTTP_TIMEOUT_MS));
+	let pending = http::Request::post(url, vec![body])
+		.add_header("Content-Type", "application/json")
+		.deadline(deadline)
+		.send()
+		.map_err(|_| OffchainError::Http)?;
+	let response = pending
+		.try_wait(deadline)
+		.map_err(|_| OffchainError::Http)?
+		.map_err(|_| OffchainError::Http)?;
+	if response.code != 200 {
+		log::warn!(target: LOG_TARGET, "unexpected status code: {}", response.code);
+		return Err(OffchainError::Http)
+	}
+	rpc_result(&response.body().collect::<Vec<u8>>())
+}
+
+/// Result of a JSON-RPC response of the external ledger
+pub fn rpc_result(body: &[u8]) -> Result<JsonValue, OffchainError> {
+	let body = sp_std::str::from_utf8(body).map_err(|_| OffchainError::InvalidResponse)?;
+	let json = lite_json::parse_json(body).map_err(|_| OffchainError::InvalidResponse)?;
+	take_field(json, "result").ok_or(OffchainError::InvalidResponse)
+}
+
+/// Index of the latest validated ledger
+fn validated_ledger_index(endpoint: &[u8]) -> Result<LedgerIndex, OffchainError> {
+	parse_validated_ledger_index(&rpc_call(endpoint, validated_ledger_request())?)
+}
+
+/// Request body for the latest validated ledger
+pub fn validated_ledger_request() -> Vec<u8> {
+	b"{\"method\":\"ledger\",\"params\":[{\"ledger_index\":\"validated\"}]}".to_vec()
+}
+
+/// Index of the latest validated ledger in the result of its request
+pub fn parse_validated_ledger_index(result: &JsonValue) -> Result<LedgerIndex, OffchainError> {
+	field(result, "ledger_index")
+		.and_then(as_u64)
+		.ok_or(OffchainError::InvalidResponse)
+}
+
+/// Payments to `door_account` on a validated ledger
+fn ledger_payments(
+	endpoint: &[u8],
+	ledger_index: LedgerIndex,
+	door_account: &[u8],
+) -> Result<Vec<LedgerPayment>, OffchainError> {
+	parse_ledger_payments(&rpc_call(endpoint, ledger_request(ledger_index))?, door_account)
+}
+
+/// Request body for a ledger with its transactions
+pub fn ledger_request(ledger_index: LedgerIndex) -> Vec<u8> {
+	[
+		&b"{\"method\":\"ledger\",\"params\":[{\"ledger_index\":"[..],
+		&encode_decimal(ledger_index),
+		b",\"transactions\":true,\"expand\":true}]}",
+	]
+	.concat()
+}
+
+/// Payments to `door_account` in the result of a ledger request, which must be validated
+pub fn parse_ledger_payments(
+	result: &JsonValue,
+	door_account: &[u8],
+) -> Result<Vec<LedgerPayment>, OffchainError> {
+	if !matches!(field(result, "validated"), Some(JsonValue::Boolean(true))) {
+		return Err(OffchainError::NotValidated)
+	}
+	let ledger = field(result, "ledger").ok_or(OffchainError::InvalidResponse)?;
+	let close_time = field(ledger, "close_time")
+		.and_then(as_u64)
+		.ok_or(OffchainError::InvalidResponse)?;
+	let transactions = match field(ledger, "transactions") {
+		Some(JsonValue::Array(transactions)) => transactions,
+		_ => return Err(OffchainError::InvalidResponse),
+	};
+	Ok(transactions
+		.iter()
+		.filter_map(|transaction| parse_payment(transaction, door_account, close_time))
+		.collect())
+}
+
+/// Look up a transaction on the external ledger and compare it with the one submitted
+/// Returns the reason to challenge it and the discrepancy as evidence, `None` if it matches
+/// A transaction not found is only challenged once the node has searched its whole ledger,
+/// otherwise it is looked up again later
+fn verify_transaction(
+	endpoint: &[u8],
+	door_account: &[u8],
+	ledger_index: LedgerIndex,
+	transaction: &Transaction,
+) -> Result<Option<(ChallengeReason, Vec<u8>)>, OffchainError> {
+	let not_found = Ok(Some((ChallengeReason::NotOnLedger, b"transaction not found".to_vec())));
+	// Only the low 32 bytes of the hash are used by the external ledger
+	let hash = transaction.transaction_hash.as_bytes();
+	if hash[..32].iter().any(|byte| *byte != 0) {
+		return not_found
+	}
+	let body = [
+		&b"{\"method\":\"tx\",\"params\":[{\"transaction\":\""[..],
+		&encode_hex(&hash[32..]),
+		b"\",\"binary\":false,\"min_ledger\":",
+		&encode_decimal(ledger_index),
+		b",\"max_ledger\":",
+		&encode_decimal(ledger_index),
+		b"}]}",
+	]
+	.concat();
+	let result = rpc_call(endpoint, body)?;
+	if field(&result, "error").and_then(as_bytes).as_deref() == Some(&b"txnNotFound"[..]) {
+		if matches!(field(&result, "searched_all"), Some(JsonValue::Boolean(true))) {
+			return not_found
+		}
+		return Err(OffchainError::NotValidated)
+	}
+	if !matches!(field(&result, "validated"), Some(JsonValue::Boolean(true))) {
+		return Err(OffchainError::NotValidated)
+	}
+
+	if !is_payment_to(&result, door_account) {
+		return Ok(Some((ChallengeReason::NotOnLedger, b"not a payment to the door".to_vec())))
+	}
+	let meta = payment_meta(&result).ok_or(OffchainError::InvalidResponse)?;
+	let result_code = field(meta, "TransactionResult")
+		.and_then(as_bytes)
+		.ok_or(OffchainError::InvalidResponse)?;
+	if result_code != b"tesSUCCESS" {
+		return Ok(Some((
+			ChallengeReason::NotOnLedger,
+			[&b"ledger result "[..], &result_code].concat(),
+		)))
+	}
+	let on_ledger_index = field(&result, "ledger_index")
+		.and_then(as_u64)
+		.ok_or(OffchainError::InvalidResponse)?;
+	if on_ledger_index != ledger_index {
+		let evidence = [&b"ledger index "[..], &encode_decimal(on_ledger_index)].concat();
+		return Ok(Some((ChallengeReason::Other, evidence)))
+	}
+
+	let TxData::Payment { amount, address, currency } = &transaction.transaction;
+	let delivered = field(meta, "delivered_amount").and_then(parse_amount);
+	let (ledger_amount, ledger_currency) = match delivered {
+		Some(delivered) => delivered,
+		None =>
+			return Ok(Some((ChallengeReason::AmountMismatch, b"ledger amount unknown".to_vec()))),
+	};
+	if ledger_currency.code != currency.code {
+		let evidence = [&b"ledger currency "[..], &encode_hex(&ledger_currency.code)].concat();
+		return Ok(Some((ChallengeReason::CurrencyMismatch, evidence)))
+	}
+	if ledger_currency.issuer != currency.issuer {
+		let evidence =
+			[&b"ledger issuer "[..], &encode_hex(ledger_currency.issuer.as_bytes())].concat();
+		return Ok(Some((ChallengeReason::CurrencyMismatch, evidence)))
+	}
+	if ledger_amount != *amount {
+		let evidence = match u64::try_from(ledger_amount) {
+			Ok(ledger_amount) => [&b"ledger amount "[..], &encode_decimal(ledger_amount)].concat(),
+			Err(_) => b"ledger amount too large".to_vec(),
+		};
+		return Ok(Some((ChallengeReason::AmountMismatch, evidence)))
+	}
+	match memo_address(&result) {
+		Some(ledger_address) if ledger_address == *address => Ok(None),
+		Some(ledger_address) => Ok(Some((
+			ChallengeReason::DestinationMismatch,
+			[&b"ledger destination "[..], &encode_hex(ledger_address.as_bytes())].concat(),
+		))),
+		None =>
+			Ok(Some((ChallengeReason::DestinationMismatch, b"ledger destination unknown".to_vec()))),
+	}
+}
+
+/// Successful payment to `door_account` with the destination account in its first memo
+/// The amount delivered is relayed, which partial payments make lower than `Amount`
+pub fn parse_payment(
+	transaction: &JsonValue,
+	door_account: &[u8],
+	close_time: u64,
+) -> Option<LedgerPayment> {
+	if !is_payment_to(transaction, door_account) {
+		return None
+	}
+	let meta = payment_meta(transaction)?;
+	if field(meta, "TransactionResult").and_then(as_bytes)? != b"tesSUCCESS" {
+		return None
+	}
+	let (amount, currency) = parse_amount(field(meta, "delivered_amount")?)?;
+	let address = memo_address(transaction)?;
+	let hash = field(transaction, "hash")
+		.and_then(as_bytes)
+		.and_then(|hash| decode_hex(&hash))?;
+	if hash.len() != 32 {
+		return None
+	}
+	let mut transaction_hash = TxHash::zero();
+	transaction_hash.as_bytes_mut()[32..].copy_from_slice(&hash);
+	Some(LedgerPayment {
+		transaction_hash,
+		transaction: TxData::Payment { amount, address, currency },
+		timestamp: close_time.saturating_add(LEDGER_EPOCH),
+	})
+}
+
+fn is_payment_to(transaction: &JsonValue, door_account: &[u8]) -> bool {
+	field(transaction, "TransactionType").and_then(as_bytes).as_deref() == Some(&b"Payment"[..]) &&
+		field(transaction, "Destination").and_then(as_bytes).as_deref() == Some(door_account)
+}
+
+/// Metadata of a transaction, under `metaData` in ledgers and `meta` when looked up alone
+fn payment_meta(transaction: &JsonValue) -> Option<&JsonValue> {
+	field(transaction, "metaData").or_else(|| field(transaction, "meta"))
+}
+
+/// Destination account of a payment, in the data of its first memo
+fn memo_address(transaction: &JsonValue) -> Option<Address> {
+	match field(transaction, "Memos") {
+		Some(JsonValue::Array(memos)) => memos.first(),
+		_ => None,
+	}
+	.and_then(|memo| field(memo, "Memo"))
+	.and_then(|memo| field(memo, "MemoData"))
+	.and_then(as_bytes)
+	.and_then(|data| decode_hex(&data))
+	.filter(|data| data.len() == 20)
+	.map(|data| Address::from_slice(&data))
+}
+
+/// Amount in drops for the native currency, or an issued currency amount object
+fn parse_amount(amount: &JsonValue) -> Option<(Balance, ExternalCurrency)> {
+	match amount {
+		JsonValue::String(_) =>
+			Some((parse_value(&as_bytes(amount)?, 0)?, ExternalCurrency::default())),
+		JsonValue::Object(_) => {
+			let code = field(amount, "currency").and_then(as_bytes)?;
+			let code = match code.len() {
+				3 if code != b"XRP" => {
+					let mut currency_code = [0u8; 20];
+					currency_code[12..15].copy_from_slice(&code);
+					currency_code
+				},
+				40 => decode_hex(&code)?.try_into().ok()?,
+				_ => return None,
+			};
+			let issuer = decode_address(&field(amount, "issuer").and_then(as_bytes)?)?;
+			let value = parse_value(&field(amount, "value").and_then(as_bytes)?, AMOUNT_DECIMALS)?;
+			Some((value, ExternalCurrency { code, issuer }))
+		},
+		_ => None,
+	}
+}
+
+/// Decimal value such as `1.5` or `2e-3` scaled by `10^decimals`
+/// `None` for negative values or ones not a whole number once scaled
+pub fn parse_value(value: &[u8], decimals: u32) -> Option<Balance> {
+	let (mantissa, exponent) = match value.iter().position(|c| *c == b'e' || *c == b'E') {
+		Some(position) => (&value[..position], parse_exponent(&value[position + 1..])?),
+		None => (value, 0),
+	};
+	let (integer, fraction) = match mantissa.iter().position(|c| *c == b'.') {
+		Some(position) => (&mantissa[..position], &mantissa[position + 1..]),
+		None => (mantissa, &[][..]),
+	};
+	if integer.is_empty() && fraction.is_empty() {
+		return None
+	}
+	let mut digits: Balance = 0;
+	for digit in integer.iter().chain(fraction) {
+		if !digit.is_ascii_digit() {
+			return None
+		}
+		digits = digits.checked_mul(10)?.checked_add((digit - b'0') as Balance)?;
+	}
+	let shift = exponent.checked_add(decimals as i32)?.checked_sub(fraction.len() as i32)?;
+	if shift >= 0 {
+		digits.checked_mul(10u128.checked_pow(shift as u32)?)
+	} else {
+		let divisor = 10u128.checked_pow(shift.unsigned_abs())?;
+		(digits % divisor == 0).then(|| digits / divisor)
+	}
+}
+
+fn parse_exponent(exponent: &[u8]) -> Option<i32> {
+	let (negative, digits) = match exponent.first() {
+		Some(b'-') => (true, &exponent[1..]),
+		Some(b'+') => (false, &exponent[1..]),
+		_ => (false, exponent),
+	};
+	if digits.is_empty() || digits.len() > 4 || !digits.iter().all(u8::is_ascii_digit) {
+		return None
+	}
+	let value = digits.iter().fold(0i32, |value, digit| value * 10 + (digit - b'0') as i32);
+	Some(if negative { -value } else { value })
+}
+
+/// Decode a classic address of the external ledger into its account id
+pub fn decode_address(address: &[u8]) -> Option<Address> {
+	let mut bytes = [0u8; 25];
+	for c in address {
+		let mut carry = ADDRESS_ALPHABET.iter().position(|a| a == c)? as u32;
+		for byte in bytes.iter_mut().rev() {
+			carry += *byte as u32 * 58;
+			*byte = carry as u8;
+			carry >>= 8;
+		}
+		if carry != 0 {
+			return None
+		}
+	}
+	let (payload, checksum) = bytes.split_at(21);
+	let hash = sp_io::hashing::sha2_256(&sp_io::hashing::sha2_256(payload));
+	if payload[0] != 0 || hash[..4] != *checksum {
+		return None
+	}
+	Some(Address::from_slice(&payload[1..]))
+}
+
+fn decode_hex(hex: &[u8]) -> Option<Vec<u8>> {
+	fn nibble(c: u8) -> Option<u8> {
+		match c {
+			b'0'..=b'9' => Some(c - b'0'),
+			b'a'..=b'f' => Some(c - b'a' + 10),
+			b'A'..=b'F' => Some(c - b'A' + 10),
+			_ => None,
+		}
+	}
+	if hex.len() % 2 != 0 {
+		return None
+	}
+	hex.chunks(2)
+		.map(|pair| Some((nibble(pair[0])? << 4) | nibble(pair[1])?))
+		.collect()
+}
+
+/// Upper case hex, as the external ledger encodes hashes
+fn encode_hex(bytes: &[u8]) -> Vec<u8> {
+	const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+	bytes
+		.iter()
+		.flat_map(|byte| [DIGITS[(byte >> 4) as usize], DIGITS[(byte & 0xf) as usize]])
+		.collect()
+}
+
+fn encode_decimal(mut n: u64) -> Vec<u8> {
+	let mut digits = vec![b'0' + (n % 10) as u8];
+	n /= 10;
+	while n > 0 {
+		digits.push(b'0' + (n % 10) as u8);
+		n /= 10;
+	}
+	digits.reverse();
+	digits
+}
+
+fn field<'a>(value: &'a JsonValue, name: &str) -> Option<&'a JsonValue> {
+	match value {
+		JsonValue::Object(fields) => fields
+			.iter()
+			.find(|(key, _)| key.iter().copied().eq(name.chars()))
+			.map(|(_, v)| v),
+		_ => None,
+	}
+}
+
+fn take_field(value: JsonValue, name: &str) -> Option<JsonValue> {
+	match value {
+		JsonValue::Object(fields) => fields
+			.into_iter()
+			.find(|(key, _)| key.iter().copied().eq(name.chars()))
+			.map(|(_, v)| v),
+		_ => None,
+	}
+}
+
+/// ASCII string value
+fn as_bytes(value: &JsonValue) -> Option<Vec<u8>> {
+	match value {
+		JsonValue::String(chars) =>
+			chars.iter().map(|c| if c.is_ascii() { Some(*c as u8) } else { None }).collect(),
+		_ => None,
+	}
+}
+
+/// Integer number, or string of digits
+fn as_u64(value: &JsonValue) -> Option<u64> {
+	match value {
+		JsonValue::Number(number)
+			if !number.negative && number.fraction_length == 0 && number.exponent == 0 =>
+			Some(number.integer),
+		JsonValue::String(_) => {
+			let digits = as_bytes(value)?;
+			if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+				return None
+			}
+			digits
+				.iter()
+				.try_fold(0u64, |n, digit| n.checked_mul(10)?.checked_add((digit - b'0') as u64))
+		},
+		_ => None,
+	}
+}
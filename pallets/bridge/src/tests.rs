@@ -4,11 +4,6 @@ use mock::*;
 use primitives::types::{AccountId, Balance};
 use sp_core::H160;
 
-/// Helper function to get the xrp balance of an address slice
-fn token_balance_of(address: &[u8]) -> u128 {
-	<Test as Config>::Assets::balance(TokenAssetId::get(), &H160::from_slice(address).into())
-}
-
 fn process_transaction(account_address: &[u8; 20]) {
 	let transaction_hash = b"6490B68F1116BFE87DDDAD4C5482D1514F9CA8B9B5B5BFD3CF81D8E68745317B";
 	let transaction_hash_1 = b"6490B68F1116BFE87DDDAD4C5482D1514F9CA8B9B5B5BFD3CF81D8E68745317C";
@@ -20,7 +15,7 @@ fn process_transaction(account_address: &[u8; 20]) {
 	Bridge::on_initialize(TxChallengePeriod::get() as u64);
 	System::set_block_number(TxChallengePeriod::get() as u64);
 
-	let balance = token_balance_of(account_address);
+	let balance = token_balance_of(create_account(account_address));
 	assert_eq!(balance, token(2000));
 }
 
@@ -34,6 +29,7 @@ fn submit_transaction(
 	let transaction = TxData::Payment {
 		amount: (i as u128 * token(1000u128)) as Balance,
 		address: H160::from_slice(account_address),
+		currency: jur_currency(),
 	};
 	assert_ok!(Bridge::submit_transaction(
 		RuntimeOrigin::signed(relayer),
@@ -49,8 +45,11 @@ fn submit_transaction_replay() {
 	new_test_ext().execute_with(|| {
 		let relayer = create_account(b"6490B68F1116BFE87DDD");
 		let transaction_hash = b"6490B68F1116BFE87DDDAD4C5482D1514F9CA8B9B5B5BFD3CF81D8E68745317B";
-		let transaction =
-			TxData::Payment { amount: 1000 as Balance, address: H160::from_low_u64_be(555) };
+		let transaction = TxData::Payment {
+			amount: 1000 as Balance,
+			address: H160::from_low_u64_be(555),
+			currency: jur_currency(),
+		};
 		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer));
 		assert_ok!(Bridge::submit_transaction(
 			RuntimeOrigin::signed(relayer),
@@ -107,11 +106,13 @@ fn process_transaction_challenge_works() {
 		assert_ok!(Bridge::submit_challenge(
 			RuntimeOrigin::signed(challenger),
 			TxHash::from_slice(transaction_hash),
+			ChallengeReason::AmountMismatch,
+			None,
 		));
 		Bridge::on_initialize(TxChallengePeriod::get() as u64);
 		System::set_block_number(TxChallengePeriod::get() as u64);
 
-		let balance = token_balance_of(tx_address);
+		let balance = token_balance_of(create_account(tx_address));
 		assert_eq!(balance, 0);
 	})
 }
@@ -128,10 +129,12 @@ fn process_transaction_failed_challenge_works() {
 		assert_ok!(Bridge::submit_challenge(
 			RuntimeOrigin::signed(challenger),
 			TxHash::from_slice(transaction_hash),
+			ChallengeReason::AmountMismatch,
+			None,
 		));
 		Bridge::on_initialize(TxChallengePeriod::get() as u64);
 		System::set_block_number(TxChallengePeriod::get() as u64);
-		let balance = token_balance_of(tx_address);
+		let balance = token_balance_of(create_account(tx_address));
 		assert_eq!(balance, 0);
 
 		assert_ok!(Bridge::failed_challenge(
@@ -141,7 +144,7 @@ fn process_transaction_failed_challenge_works() {
 
 		Bridge::on_initialize(TxChallengePeriod::get() as u64 + TxChallengePeriod::get() as u64);
 		System::set_block_number(TxChallengePeriod::get() as u64 + TxChallengePeriod::get() as u64);
-		let balance = token_balance_of(tx_address);
+		let balance = token_balance_of(create_account(tx_address));
 		assert_eq!(balance, token(1000));
 	})
 }
@@ -158,10 +161,12 @@ fn process_transaction_success_challenge_works() {
 		assert_ok!(Bridge::submit_challenge(
 			RuntimeOrigin::signed(challenger),
 			TxHash::from_slice(transaction_hash),
+			ChallengeReason::AmountMismatch,
+			None,
 		));
 		Bridge::on_initialize(TxChallengePeriod::get() as u64);
 		System::set_block_number(TxChallengePeriod::get() as u64);
-		let balance = token_balance_of(tx_address);
+		let balance = token_balance_of(create_account(tx_address));
 		assert_eq!(balance, 0);
 
 		assert_ok!(Bridge::success_challenge(
@@ -171,7 +176,7 @@ fn process_transaction_success_challenge_works() {
 
 		Bridge::on_initialize(TxChallengePeriod::get() as u64);
 		System::set_block_number(TxChallengePeriod::get() as u64);
-		let balance = token_balance_of(tx_address);
+		let balance = token_balance_of(create_account(tx_address));
 		assert_eq!(balance, token(0));
 	})
 }
@@ -205,3 +210,743 @@ fn clear_storages() {
 		assert!(<ProcessTransactionDetails<Test>>::get(tx_hash_2).is_none());
 	});
 }
+
+#[test]
+fn submit_transaction_requires_relayer_quorum() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let transaction_hash = b"6490B68F1116BFE87DDDAD4C5482D1514F9CA8B9B5B5BFD3CF81D8E68745317B";
+		let tx_address = b"6490B68F1116BFE87DDC";
+		let relayer_1 = create_account(b"6490B68F1116BFE87DD1");
+		let relayer_2 = create_account(b"6490B68F1116BFE87DD2");
+		let relayer_3 = create_account(b"6490B68F1116BFE87DD3");
+		Bridge::initialize_relayer(&vec![relayer_1, relayer_2, relayer_3]);
+		assert_ok!(Bridge::set_relayer_threshold(RuntimeOrigin::root(), 2));
+
+		submit_transaction(relayer_1, 1_000_000, transaction_hash, tx_address, 1);
+		let hash = TxHash::from_slice(transaction_hash);
+		assert!(Bridge::process_transaction_details(hash).is_none());
+		assert!(Bridge::attested(hash, &relayer_1));
+		assert!(!Bridge::attested(hash, &relayer_2));
+		System::assert_last_event(RuntimeEvent::Bridge(Event::TransactionAttested(
+			1_000_000, hash, relayer_1,
+		)));
+
+		// A relayer can only attest once
+		assert_noop!(
+			Bridge::submit_transaction(
+				RuntimeOrigin::signed(relayer_1),
+				1_000_000,
+				hash,
+				TxData::Payment {
+					amount: token(1000),
+					address: H160::from_slice(tx_address),
+					currency: jur_currency(),
+				},
+				1234
+			),
+			Error::<Test>::AlreadyAttested
+		);
+
+		// A disagreeing attestation is kept and reported but does not count towards the quorum
+		submit_transaction(relayer_2, 1_000_000, transaction_hash, tx_address, 5);
+		assert!(Bridge::process_transaction_details(hash).is_none());
+		assert!(Bridge::transaction_attestations(hash, relayer_2).is_some());
+		System::assert_has_event(RuntimeEvent::Bridge(Event::AttestationMismatch(
+			1_000_000, hash, relayer_2,
+		)));
+
+		submit_transaction(relayer_3, 1_000_000, transaction_hash, tx_address, 1);
+		assert!(Bridge::process_transaction_details(hash).is_some());
+		System::assert_has_event(RuntimeEvent::Bridge(Event::TransactionAdded(
+			1_000_000,
+			hash,
+			ProcessAt::Block(TxChallengePeriod::get() as u64 + 1),
+		)));
+
+		Bridge::on_initialize(TxChallengePeriod::get() as u64 + 1);
+		assert_eq!(token_balance_of(create_account(tx_address)), token(1000));
+	})
+}
+
+#[test]
+fn quorum_ignores_attestations_of_inactive_relayers() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let transaction_hash = b"6490B68F1116BFE87DDDAD4C5482D1514F9CA8B9B5B5BFD3CF81D8E68745317B";
+		let tx_address = b"6490B68F1116BFE87DDC";
+		let relayer_1 = create_account(b"6490B68F1116BFE87DD1");
+		let relayer_2 = create_account(b"6490B68F1116BFE87DD2");
+		let relayer_3 = create_account(b"6490B68F1116BFE87DD3");
+		let relayer_4 = create_account(b"6490B68F1116BFE87DDE");
+		Bridge::initialize_relayer(&vec![relayer_1, relayer_2, relayer_3, relayer_4]);
+		assert_ok!(Bridge::set_relayer_threshold(RuntimeOrigin::root(), 2));
+		let hash = TxHash::from_slice(transaction_hash);
+
+		submit_transaction(relayer_1, 1, transaction_hash, tx_address, 1);
+		assert_ok!(Bridge::remove_relayer(RuntimeOrigin::root(), relayer_1));
+		submit_transaction(relayer_2, 1, transaction_hash, tx_address, 1);
+		assert!(Bridge::process_transaction_details(hash).is_none());
+
+		// Deactivated as by a slash
+		<Relayer<Test>>::insert(relayer_2, false);
+		submit_transaction(relayer_3, 1, transaction_hash, tx_address, 1);
+		assert!(Bridge::process_transaction_details(hash).is_none());
+
+		submit_transaction(relayer_4, 1, transaction_hash, tx_address, 1);
+		assert!(Bridge::process_transaction_details(hash).is_some());
+	})
+}
+
+#[test]
+fn submit_transaction_limits_attestations() {
+	new_test_ext().execute_with(|| {
+		MaxRelayers::set(2);
+		let transaction_hash = b"6490B68F1116BFE87DDDAD4C5482D1514F9CA8B9B5B5BFD3CF81D8E68745317B";
+		let tx_address = b"6490B68F1116BFE87DDC";
+		let relayer_1 = create_account(b"6490B68F1116BFE87DD1");
+		let relayer_2 = create_account(b"6490B68F1116BFE87DD2");
+		let relayer_3 = create_account(b"6490B68F1116BFE87DD3");
+		Bridge::initialize_relayer(&vec![relayer_1, relayer_2, relayer_3]);
+		assert_ok!(Bridge::set_relayer_threshold(RuntimeOrigin::root(), 3));
+
+		submit_transaction(relayer_1, 1, transaction_hash, tx_address, 1);
+		submit_transaction(relayer_2, 1, transaction_hash, tx_address, 1);
+		assert_noop!(
+			Bridge::submit_transaction(
+				RuntimeOrigin::signed(relayer_3),
+				1,
+				TxHash::from_slice(transaction_hash),
+				TxData::Payment {
+					amount: token(1000),
+					address: H160::from_slice(tx_address),
+					currency: jur_currency(),
+				},
+				1234
+			),
+			Error::<Test>::TooManyAttestations
+		);
+	})
+}
+
+#[test]
+fn attestations_of_inactive_relayers_do_not_fill_the_limit() {
+	new_test_ext().execute_with(|| {
+		MaxRelayers::set(2);
+		let transaction_hash = b"6490B68F1116BFE87DDDAD4C5482D1514F9CA8B9B5B5BFD3CF81D8E68745317B";
+		let tx_address = b"6490B68F1116BFE87DDC";
+		let relayer_1 = create_account(b"6490B68F1116BFE87DD1");
+		let relayer_2 = create_account(b"6490B68F1116BFE87DD2");
+		let relayer_3 = create_account(b"6490B68F1116BFE87DD3");
+		Bridge::initialize_relayer(&vec![relayer_1, relayer_2, relayer_3]);
+		assert_ok!(Bridge::set_relayer_threshold(RuntimeOrigin::root(), 2));
+		let hash = TxHash::from_slice(transaction_hash);
+
+		// Junk attestations of two relayers removed since fill the limit
+		submit_transaction(relayer_1, 1, transaction_hash, tx_address, 5);
+		submit_transaction(relayer_2, 1, transaction_hash, tx_address, 7);
+		assert_ok!(Bridge::remove_relayer(RuntimeOrigin::root(), relayer_1));
+		assert_ok!(Bridge::remove_relayer(RuntimeOrigin::root(), relayer_2));
+
+		// They are dropped instead of blocking the active relayers
+		submit_transaction(relayer_3, 1, transaction_hash, tx_address, 1);
+		assert!(!Bridge::attested(hash, &relayer_1));
+		assert!(!Bridge::attested(hash, &relayer_2));
+		assert!(Bridge::attested(hash, &relayer_3));
+
+		let relayer_4 = create_account(b"6490B68F1116BFE87DDE");
+		Bridge::initialize_relayer(&vec![relayer_4]);
+		submit_transaction(relayer_4, 1, transaction_hash, tx_address, 1);
+		assert!(Bridge::process_transaction_details(hash).is_some());
+	})
+}
+
+#[test]
+fn set_relayer_threshold_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Bridge::relayer_threshold(), 1);
+		assert_noop!(
+			Bridge::set_relayer_threshold(RuntimeOrigin::root(), 0),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			Bridge::set_relayer_threshold(
+				RuntimeOrigin::signed(create_account(b"6490B68F1116BFE87DDD")),
+				2
+			),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_ok!(Bridge::set_relayer_threshold(RuntimeOrigin::root(), 3));
+		assert_eq!(Bridge::relayer_threshold(), 3);
+	})
+}
+
+#[test]
+fn register_currency_works() {
+	new_test_ext().execute_with(|| {
+		let usd = currency(b"USD");
+		assert_eq!(Bridge::currency_registry(jur_currency()), Some(TokenAssetId::get()));
+		assert_eq!(Bridge::currency_registry(usd), None);
+
+		assert_noop!(
+			Bridge::register_currency(
+				RuntimeOrigin::signed(create_account(b"6490B68F1116BFE87DDD")),
+				usd,
+				3
+			),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_ok!(Bridge::register_currency(RuntimeOrigin::root(), usd, 3));
+		assert_eq!(Bridge::currency_registry(usd), Some(3));
+
+		assert_ok!(Bridge::deregister_currency(RuntimeOrigin::root(), usd));
+		assert_eq!(Bridge::currency_registry(usd), None);
+		assert_noop!(
+			Bridge::deregister_currency(RuntimeOrigin::root(), usd),
+			Error::<Test>::UnknownCurrency
+		);
+	})
+}
+
+#[test]
+fn process_transaction_mints_registered_asset() {
+	new_test_ext().execute_with(|| {
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		let destination = create_account(b"6490B68F1116BFE87DDC");
+		let usd = currency(b"USD");
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 3, relayer, true, 1));
+		Bridge::initialize_relayer(&vec![relayer]);
+
+		let payment = |currency| TxData::Payment {
+			amount: token(10),
+			address: H160::from_slice(b"6490B68F1116BFE87DDC"),
+			currency,
+		};
+		assert_noop!(
+			Bridge::submit_transaction(
+				RuntimeOrigin::signed(relayer),
+				1,
+				TxHash::from_low_u64_be(1),
+				payment(usd),
+				1234
+			),
+			Error::<Test>::UnknownCurrency
+		);
+
+		assert_ok!(Bridge::register_currency(RuntimeOrigin::root(), usd, 3));
+		assert_ok!(Bridge::submit_transaction(
+			RuntimeOrigin::signed(relayer),
+			1,
+			TxHash::from_low_u64_be(1),
+			payment(usd),
+			1234
+		));
+		Bridge::on_initialize(TxChallengePeriod::get() as u64);
+
+		assert_eq!(<Test as Config>::Assets::balance(3, &destination), token(10));
+		assert_eq!(<Test as Config>::Assets::balance(TokenAssetId::get(), &destination), 0);
+	})
+}
+
+#[test]
+fn failed_mint_can_be_retried() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let account_address = b"6490B68F1116BFE87DDC";
+		let transaction_hash = b"6490B68F1116BFE87DDDAD4C5482D1514F9CA8B9B5B5BFD3CF81D8E68745317B";
+		let tx_hash = TxHash::from_slice(transaction_hash);
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		Bridge::initialize_relayer(&vec![relayer]);
+		submit_transaction(relayer, 1, transaction_hash, account_address, 1);
+
+		// The currency is no longer bridged when the transaction gets processed
+		assert_ok!(Bridge::deregister_currency(RuntimeOrigin::root(), jur_currency()));
+		Bridge::on_initialize(TxChallengePeriod::get() as u64 + 1);
+
+		let error: DispatchError = Error::<Test>::UnknownCurrency.into();
+		assert_eq!(token_balance_of(create_account(account_address)), 0);
+		assert_eq!(Bridge::failed_transactions(tx_hash), Some(error));
+		assert!(Bridge::process_transaction_details(tx_hash).is_some());
+		System::assert_last_event(RuntimeEvent::Bridge(Event::MintFailed(1, tx_hash, error)));
+		assert!(!System::events()
+			.iter()
+			.any(|record| matches!(record.event, RuntimeEvent::Bridge(Event::Processed(..)))));
+
+		// Retrying keeps the transaction failed until the mint succeeds
+		assert_noop!(
+			Bridge::retry_failed(RuntimeOrigin::signed(relayer), tx_hash),
+			Error::<Test>::UnknownCurrency
+		);
+		assert_ok!(Bridge::register_currency(
+			RuntimeOrigin::root(),
+			jur_currency(),
+			TokenAssetId::get()
+		));
+		assert_ok!(Bridge::retry_failed(RuntimeOrigin::signed(relayer), tx_hash));
+
+		assert_eq!(token_balance_of(create_account(account_address)), token(1000));
+		assert_eq!(Bridge::failed_transactions(tx_hash), None);
+		System::assert_last_event(RuntimeEvent::Bridge(Event::Processed(1, tx_hash)));
+		assert_noop!(
+			Bridge::retry_failed(RuntimeOrigin::signed(relayer), tx_hash),
+			Error::<Test>::TransactionNotFailed
+		);
+	})
+}
+
+#[test]
+fn submit_transaction_replay_after_pruning() {
+	new_test_ext().execute_with(|| {
+		let account_address = b"6490B68F1116BFE87DDC";
+		let transaction_hash = b"6490B68F1116BFE87DDDAD4C5482D1514F9CA8B9B5B5BFD3CF81D8E68745317B";
+		let tx_hash = TxHash::from_slice(transaction_hash);
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		Bridge::initialize_relayer(&vec![relayer]);
+		submit_transaction(relayer, 1, transaction_hash, account_address, 1);
+
+		let process_block = TxChallengePeriod::get() as u64;
+		System::set_block_number(process_block);
+		Bridge::on_initialize(process_block);
+		let clear_block = process_block + ClearTxPeriod::get() as u64;
+		System::set_block_number(clear_block);
+		Bridge::on_initialize(clear_block);
+
+		assert!(Bridge::process_transaction_details(tx_hash).is_none());
+		assert!(Bridge::settled_transaction_details(clear_block).is_none());
+		assert_eq!(Bridge::transaction_status(tx_hash), Some(TxStatus::Settled));
+		assert_noop!(
+			Bridge::submit_transaction(
+				RuntimeOrigin::signed(relayer),
+				1,
+				tx_hash,
+				TxData::Payment {
+					amount: token(1000),
+					address: H160::from_slice(account_address),
+					currency: jur_currency(),
+				},
+				1234
+			),
+			Error::<Test>::TxReplay
+		);
+		assert_eq!(token_balance_of(create_account(account_address)), token(1000));
+	})
+}
+
+#[test]
+fn process_transaction_carries_over_beyond_block_limit() {
+	new_test_ext().execute_with(|| {
+		MaxProcessPerBlock::set(2);
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		Bridge::initialize_relayer(&vec![relayer]);
+		let tx_hashes: Vec<TxHash> = (1..=5).map(TxHash::from_low_u64_be).collect();
+		for (i, transaction_hash) in tx_hashes.iter().enumerate() {
+			assert_ok!(Bridge::submit_transaction(
+				RuntimeOrigin::signed(relayer),
+				i as u64,
+				*transaction_hash,
+				TxData::Payment {
+					amount: token(1000),
+					address: H160::from_low_u64_be(555),
+					currency: jur_currency(),
+				},
+				1234
+			));
+		}
+		let settled = |transaction_hash: &TxHash| {
+			Bridge::transaction_status(transaction_hash) == Some(TxStatus::Settled)
+		};
+
+		let process_block = TxChallengePeriod::get() as u64;
+		System::set_block_number(process_block);
+		Bridge::on_initialize(process_block);
+		assert_eq!(tx_hashes.iter().filter(|hash| settled(*hash)).count(), 2);
+		assert_eq!(Bridge::carry_over_depth(), 3);
+		assert_eq!(Bridge::carry_over_queue(0), Some(tx_hashes[2]));
+
+		// Carried over transactions are processed first, in submission order
+		System::set_block_number(process_block + 1);
+		Bridge::on_initialize(process_block + 1);
+		assert!(settled(&tx_hashes[2]) && settled(&tx_hashes[3]));
+		assert!(!settled(&tx_hashes[4]));
+		assert_eq!(Bridge::carry_over_depth(), 1);
+		assert_eq!(Bridge::carry_over_cursor(), (2, 3));
+	})
+}
+
+#[test]
+fn on_idle_drains_carry_over_queue() {
+	new_test_ext().execute_with(|| {
+		MaxProcessPerBlock::set(0);
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		let account_address = b"6490B68F1116BFE87DDC";
+		let transaction_hash = b"6490B68F1116BFE87DDDAD4C5482D1514F9CA8B9B5B5BFD3CF81D8E68745317B";
+		Bridge::initialize_relayer(&vec![relayer]);
+		submit_transaction(relayer, 1, transaction_hash, account_address, 1);
+
+		let process_block = TxChallengePeriod::get() as u64;
+		System::set_block_number(process_block);
+		Bridge::on_initialize(process_block);
+		assert_eq!(Bridge::carry_over_depth(), 1);
+
+		// Not enough weight left to process a transaction
+		assert_eq!(Bridge::on_idle(process_block, Weight::from_ref_time(1)), Weight::zero());
+		assert_eq!(Bridge::carry_over_depth(), 1);
+
+		let weight = Bridge::on_idle(process_block, Weight::from_ref_time(u64::MAX));
+		assert_eq!(weight, <() as WeightInfo>::process_tx(1, MaxRelayers::get()));
+		assert_eq!(Bridge::carry_over_depth(), 0);
+		assert_eq!(token_balance_of(create_account(account_address)), token(1000));
+	})
+}
+
+#[test]
+fn submit_transaction_fails_when_block_is_full() {
+	new_test_ext().execute_with(|| {
+		MaxTransactionsPerBlock::set(1);
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		Bridge::initialize_relayer(&vec![relayer]);
+		let payment = TxData::Payment {
+			amount: token(1000),
+			address: H160::from_low_u64_be(555),
+			currency: jur_currency(),
+		};
+		assert_ok!(Bridge::submit_transaction(
+			RuntimeOrigin::signed(relayer),
+			1,
+			TxHash::from_low_u64_be(1),
+			payment.clone(),
+			1234
+		));
+		assert_noop!(
+			Bridge::submit_transaction(
+				RuntimeOrigin::signed(relayer),
+				2,
+				TxHash::from_low_u64_be(2),
+				payment.clone(),
+				1234
+			),
+			Error::<Test>::TooManyTransactions
+		);
+
+		// The next block has room again
+		System::set_block_number(1);
+		assert_ok!(Bridge::submit_transaction(
+			RuntimeOrigin::signed(relayer),
+			2,
+			TxHash::from_low_u64_be(2),
+			payment,
+			1234
+		));
+	})
+}
+
+#[test]
+fn settled_transactions_overflow_to_next_clear_block() {
+	new_test_ext().execute_with(|| {
+		MaxTransactionsPerBlock::set(1);
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		Bridge::initialize_relayer(&vec![relayer]);
+		for i in 1..=2 {
+			System::set_block_number(i - 1);
+			assert_ok!(Bridge::submit_transaction(
+				RuntimeOrigin::signed(relayer),
+				i,
+				TxHash::from_low_u64_be(i),
+				TxData::Payment {
+					amount: token(1000),
+					address: H160::from_low_u64_be(555),
+					currency: jur_currency(),
+				},
+				1234
+			));
+		}
+
+		// Both transactions are settled while the chain is at the same block
+		let process_block = TxChallengePeriod::get() as u64;
+		System::set_block_number(process_block);
+		Bridge::on_initialize(process_block);
+		Bridge::on_initialize(process_block + 1);
+
+		let clear_block = process_block + ClearTxPeriod::get() as u64;
+		assert_eq!(
+			Bridge::settled_transaction_details(clear_block).unwrap(),
+			vec![TxHash::from_low_u64_be(1)]
+		);
+		assert_eq!(
+			Bridge::settled_transaction_details(clear_block + 1).unwrap(),
+			vec![TxHash::from_low_u64_be(2)]
+		);
+	})
+}
+
+#[test]
+fn submit_transaction_checks_timestamp() {
+	new_test_ext().execute_with(|| {
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		Bridge::initialize_relayer(&vec![relayer]);
+		let payment = TxData::Payment {
+			amount: token(1000),
+			address: H160::from_low_u64_be(555),
+			currency: jur_currency(),
+		};
+		let submit = |i: u64, timestamp: u64| {
+			Bridge::submit_transaction(
+				RuntimeOrigin::signed(relayer),
+				i,
+				TxHash::from_low_u64_be(i),
+				payment.clone(),
+				timestamp,
+			)
+		};
+
+		assert_noop!(
+			submit(1, GENESIS_TIME + MaxTimestampDrift::get() + 1),
+			Error::<Test>::TimestampInFuture
+		);
+		assert_noop!(
+			submit(1, GENESIS_TIME - MaxTransactionAge::get() - 1),
+			Error::<Test>::TimestampTooOld
+		);
+		assert_ok!(submit(1, GENESIS_TIME + MaxTimestampDrift::get()));
+		assert_ok!(submit(2, GENESIS_TIME - MaxTransactionAge::get()));
+	})
+}
+
+#[test]
+fn settled_transactions_are_cleared_without_room_left() {
+	new_test_ext().execute_with(|| {
+		MaxTransactionsPerBlock::set(1);
+		Bridge::initialize_relayer(&vec![relayer()]);
+		let transaction_hash = TxHash::from_low_u64_be(1);
+		submit_payment(transaction_hash, token(1000), H160::from_low_u64_be(1));
+
+		let process_at = TxChallengePeriod::get() as u64;
+		let clear_at = process_at + ClearTxPeriod::get() as u64;
+		for offset in 0..MAX_RESCHEDULE_BLOCKS as u64 {
+			assert_ok!(SettledTransactionDetails::<Test>::try_append(
+				clear_at + offset,
+				TxHash::from_low_u64_be(100 + offset)
+			));
+		}
+		System::set_block_number(process_at);
+		Bridge::on_initialize(process_at);
+
+		assert_eq!(Bridge::transaction_status(transaction_hash), Some(TxStatus::Settled));
+		assert_eq!(Bridge::process_transaction_details(transaction_hash), None);
+	})
+}
+
+#[test]
+fn admitted_transactions_can_be_submitted_past_max_age() {
+	new_test_ext().execute_with(|| {
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		Bridge::initialize_relayer(&vec![relayer]);
+		let transaction_hash = TxHash::from_low_u64_be(1);
+		let submit = || {
+			Bridge::submit_transaction(
+				RuntimeOrigin::signed(relayer),
+				1,
+				transaction_hash,
+				TxData::Payment {
+					amount: token(1000),
+					address: H160::from_low_u64_be(555),
+					currency: jur_currency(),
+				},
+				GENESIS_TIME - MaxTransactionAge::get() - 1,
+			)
+		};
+		// Deposit made before an outage of the bridge
+		assert_noop!(submit(), Error::<Test>::TimestampTooOld);
+
+		assert_noop!(
+			Bridge::admit_transaction(RuntimeOrigin::signed(relayer), transaction_hash),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_ok!(Bridge::admit_transaction(RuntimeOrigin::root(), transaction_hash));
+		assert_ok!(submit());
+		assert!(matches!(Bridge::transaction_status(transaction_hash), Some(TxStatus::Pending(_))));
+		assert!(!AdmittedTransactions::<Test>::contains_key(transaction_hash));
+		assert_noop!(
+			Bridge::admit_transaction(RuntimeOrigin::root(), transaction_hash),
+			Error::<Test>::TxReplay
+		);
+	})
+}
+
+#[test]
+fn wall_clock_challenge_period_processes_by_time() {
+	new_test_ext().execute_with(|| {
+		WallClockChallengePeriod::set(Some(600));
+		let account_address = b"6490B68F1116BFE87DDC";
+		let transaction_hash = b"6490B68F1116BFE87DDDAD4C5482D1514F9CA8B9B5B5BFD3CF81D8E68745317B";
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		Bridge::initialize_relayer(&vec![relayer]);
+		submit_transaction(relayer, 1, transaction_hash, account_address, 1);
+
+		let process_time = GENESIS_TIME + 600;
+		assert_eq!(
+			Bridge::transaction_status(TxHash::from_slice(transaction_hash)),
+			Some(TxStatus::Pending(ProcessAt::Time(process_time)))
+		);
+		assert!(Bridge::process_transaction(TxChallengePeriod::get() as u64).is_none());
+
+		// Blocks alone do not end the challenge period
+		let process_block = TxChallengePeriod::get() as u64;
+		System::set_block_number(process_block);
+		Bridge::on_initialize(process_block);
+		assert_eq!(token_balance_of(create_account(account_address)), 0);
+
+		TimestampPallet::set_timestamp((process_time - 1) * 1_000);
+		Bridge::on_initialize(process_block + 1);
+		assert_eq!(token_balance_of(create_account(account_address)), 0);
+
+		TimestampPallet::set_timestamp(process_time * 1_000);
+		Bridge::on_initialize(process_block + 2);
+		assert_eq!(token_balance_of(create_account(account_address)), token(1000));
+		assert!(Bridge::timed_process_transaction(process_time).is_none());
+		assert_eq!(Bridge::timed_process_cursor(), Some(process_time + 1));
+	})
+}
+
+#[test]
+fn wall_clock_processing_catches_up_over_blocks() {
+	new_test_ext().execute_with(|| {
+		WallClockChallengePeriod::set(Some(600));
+		MaxTimedScanPerBlock::set(100);
+		let account_address = b"6490B68F1116BFE87DDC";
+		let transaction_hash = b"6490B68F1116BFE87DDDAD4C5482D1514F9CA8B9B5B5BFD3CF81D8E68745317B";
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		Bridge::initialize_relayer(&vec![relayer]);
+		submit_transaction(relayer, 1, transaction_hash, account_address, 1);
+
+		// The chain stalled, each block scans at most `MaxTimedScanPerBlock` seconds, which are
+		// not charged as processed transactions
+		TimestampPallet::set_timestamp((GENESIS_TIME + 1_000) * 1_000);
+		for block in 1..=6 {
+			assert_eq!(
+				Bridge::process_tx(block),
+				<() as WeightInfo>::process_tx(0, MaxRelayers::get())
+			);
+			assert_eq!(token_balance_of(create_account(account_address)), 0);
+			assert_eq!(Bridge::timed_process_cursor(), Some(GENESIS_TIME + block * 100));
+		}
+		Bridge::on_initialize(7);
+		assert_eq!(token_balance_of(create_account(account_address)), token(1000));
+	})
+}
+
+#[test]
+fn set_challenge_period_tiers_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		let tiers = vec![(token(1_000), 200), (token(100_000), 1_000)];
+
+		assert_noop!(
+			Bridge::set_challenge_period_tiers(RuntimeOrigin::signed(relayer), tiers.clone()),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_noop!(
+			Bridge::set_challenge_period_tiers(
+				RuntimeOrigin::root(),
+				vec![(token(100_000), 1_000), (token(1_000), 200)]
+			),
+			Error::<Test>::UnsortedChallengePeriodTiers
+		);
+		assert_noop!(
+			Bridge::set_challenge_period_tiers(
+				RuntimeOrigin::root(),
+				(1..=5).map(|i| (token(i), 200)).collect()
+			),
+			Error::<Test>::TooManyChallengePeriodTiers
+		);
+
+		assert_ok!(Bridge::set_challenge_period_tiers(RuntimeOrigin::root(), tiers.clone()));
+		assert_eq!(Bridge::challenge_period_tiers().into_inner(), tiers.clone());
+		System::assert_last_event(RuntimeEvent::Bridge(Event::ChallengePeriodTiersSet(tiers)));
+	})
+}
+
+#[test]
+fn set_challenge_period_tiers_rejects_invalid_tiers() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Bridge::set_challenge_period_tiers(
+				RuntimeOrigin::root(),
+				vec![(token(1_000), 200), (token(1_000), 1_000)]
+			),
+			Error::<Test>::UnsortedChallengePeriodTiers
+		);
+		assert_noop!(
+			Bridge::set_challenge_period_tiers(
+				RuntimeOrigin::root(),
+				vec![(token(1_000), 200), (token(100_000), 0)]
+			),
+			Error::<Test>::InvalidChallengePeriod
+		);
+		assert_eq!(Bridge::challenge_period_tiers().len(), 0);
+	})
+}
+
+#[test]
+fn challenge_period_depends_on_amount() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		Bridge::initialize_relayer(&vec![relayer]);
+		assert_ok!(Bridge::set_challenge_period_tiers(
+			RuntimeOrigin::root(),
+			vec![(token(1_000), 200), (token(100_000), 1_000)]
+		));
+
+		// Below the first tier, at each threshold and above the last one
+		let scheduled: Vec<(u64, Balance, u64)> = vec![
+			(1, token(999), 1 + TxChallengePeriod::get() as u64),
+			(2, token(1_000), 201),
+			(3, token(99_999), 201),
+			(4, token(100_000), 1_001),
+			(5, token(1_000_000), 1_001),
+		];
+		for (i, amount, process_block) in scheduled {
+			assert_ok!(Bridge::submit_transaction(
+				RuntimeOrigin::signed(relayer),
+				i,
+				TxHash::from_low_u64_be(i),
+				TxData::Payment {
+					amount,
+					address: H160::from_low_u64_be(555),
+					currency: jur_currency(),
+				},
+				1234
+			));
+			assert_eq!(
+				Bridge::transaction_status(TxHash::from_low_u64_be(i)),
+				Some(TxStatus::Pending(ProcessAt::Block(process_block)))
+			);
+			System::assert_last_event(RuntimeEvent::Bridge(Event::TransactionAdded(
+				i,
+				TxHash::from_low_u64_be(i),
+				ProcessAt::Block(process_block),
+			)));
+		}
+	})
+}
+
+#[test]
+fn wall_clock_challenge_period_depends_on_amount() {
+	new_test_ext().execute_with(|| {
+		WallClockChallengePeriod::set(Some(600));
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		Bridge::initialize_relayer(&vec![relayer]);
+		assert_ok!(Bridge::set_challenge_period_tiers(
+			RuntimeOrigin::root(),
+			vec![(token(1_000), 3_600)]
+		));
+		submit_transaction(relayer, 1, &[1; 64], b"6490B68F1116BFE87DDC", 1);
+
+		assert_eq!(
+			Bridge::transaction_status(TxHash::from_slice(&[1; 64])),
+			Some(TxStatus::Pending(ProcessAt::Time(GENESIS_TIME + 3_600)))
+		);
+	})
+}
@@ -0,0 +1,352 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::*;
+use sp_core::H160;
+
+fn payment() -> TxData {
+	TxData::Payment {
+		amount: token(1000),
+		address: H160::from_low_u64_be(555),
+		currency: jur_currency(),
+	}
+}
+
+fn submit_transaction(i: u64) -> TxHash {
+	let transaction_hash = TxHash::from_low_u64_be(i);
+	submit_payment(transaction_hash, token(1000), H160::from_low_u64_be(555));
+	transaction_hash
+}
+
+fn processed(transaction_hash: TxHash) -> bool {
+	System::events().iter().any(|record| match record.event {
+		RuntimeEvent::Bridge(Event::Processed(_, hash)) => hash == transaction_hash,
+		_ => false,
+	})
+}
+
+#[test]
+fn set_bridge_mode_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let mode = BridgeMode { challenges_paused: true, ..Default::default() };
+
+		assert_noop!(
+			Bridge::set_bridge_mode(RuntimeOrigin::signed(relayer()), mode),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_eq!(Bridge::bridge_mode(), BridgeMode::default());
+
+		assert_ok!(Bridge::set_bridge_mode(RuntimeOrigin::root(), mode));
+		assert_eq!(Bridge::bridge_mode(), mode);
+		System::assert_last_event(RuntimeEvent::Bridge(Event::BridgeModeSet(mode)));
+
+		assert_ok!(Bridge::set_bridge_mode(RuntimeOrigin::root(), BridgeMode::default()));
+		assert_eq!(Bridge::bridge_mode(), BridgeMode::default());
+	})
+}
+
+#[test]
+fn paused_switches_reject_calls() {
+	new_test_ext().execute_with(|| {
+		Bridge::initialize_relayer(&vec![relayer()]);
+		let transaction_hash = submit_transaction(1);
+		let who = create_account(b"6490B68F1116BFE87DDC");
+		assert_ok!(<Test as Config>::Assets::mint_into(TokenAssetId::get(), &who, token(1)));
+
+		assert_ok!(Bridge::set_bridge_mode(
+			RuntimeOrigin::root(),
+			BridgeMode {
+				inbound_submission_paused: true,
+				inbound_processing_paused: true,
+				challenges_paused: true,
+				outbound_paused: true,
+			}
+		));
+		assert_noop!(
+			Bridge::submit_transaction(
+				RuntimeOrigin::signed(relayer()),
+				2,
+				TxHash::from_low_u64_be(2),
+				payment(),
+				1234
+			),
+			Error::<Test>::InboundSubmissionPaused
+		);
+		assert_noop!(
+			Bridge::submit_challenge(
+				RuntimeOrigin::signed(who),
+				transaction_hash,
+				ChallengeReason::Other,
+				None
+			),
+			Error::<Test>::ChallengesPaused
+		);
+		assert_noop!(
+			Bridge::retry_failed(RuntimeOrigin::signed(who), transaction_hash),
+			Error::<Test>::InboundProcessingPaused
+		);
+		assert_noop!(
+			Bridge::withdraw(RuntimeOrigin::signed(who), token(1), H160::from_low_u64_be(1)),
+			Error::<Test>::OutboundPaused
+		);
+
+		// Only the paused parts are stopped
+		assert_ok!(Bridge::set_bridge_mode(
+			RuntimeOrigin::root(),
+			BridgeMode { outbound_paused: true, ..Default::default() }
+		));
+		submit_transaction(2);
+		assert_noop!(
+			Bridge::withdraw(RuntimeOrigin::signed(who), token(1), H160::from_low_u64_be(1)),
+			Error::<Test>::OutboundPaused
+		);
+	})
+}
+
+#[test]
+fn paused_processing_keeps_queue_order() {
+	new_test_ext().execute_with(|| {
+		Bridge::initialize_relayer(&vec![relayer()]);
+		let first = submit_transaction(1);
+		System::set_block_number(1);
+		let second = submit_transaction(2);
+		assert_ok!(Bridge::set_bridge_mode(
+			RuntimeOrigin::root(),
+			BridgeMode { inbound_processing_paused: true, ..Default::default() }
+		));
+
+		let process_block = TxChallengePeriod::get() as u64;
+		for n in process_block..process_block + 2 {
+			System::set_block_number(n);
+			Bridge::on_initialize(n);
+			assert_eq!(Bridge::on_idle(n, Weight::MAX), Weight::zero());
+		}
+		assert!(!processed(first));
+		assert!(!processed(second));
+		assert_eq!(Bridge::carry_over_depth(), 2);
+		assert_eq!(
+			Bridge::transaction_status(first),
+			Some(TxStatus::Pending(ProcessAt::Block(process_block)))
+		);
+
+		assert_ok!(Bridge::set_bridge_mode(RuntimeOrigin::root(), BridgeMode::default()));
+		System::reset_events();
+		System::set_block_number(process_block + 2);
+		Bridge::on_initialize(process_block + 2);
+
+		assert_eq!(Bridge::carry_over_depth(), 0);
+		let processed_order: Vec<TxHash> = System::events()
+			.into_iter()
+			.filter_map(|record| match record.event {
+				RuntimeEvent::Bridge(Event::Processed(_, hash)) => Some(hash),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(processed_order, vec![first, second]);
+	})
+}
+
+#[test]
+fn paused_processing_keeps_wall_clock_queue() {
+	new_test_ext().execute_with(|| {
+		WallClockChallengePeriod::set(Some(600));
+		Bridge::initialize_relayer(&vec![relayer()]);
+		let transaction_hash = submit_transaction(1);
+		assert_ok!(Bridge::set_bridge_mode(
+			RuntimeOrigin::root(),
+			BridgeMode { inbound_processing_paused: true, ..Default::default() }
+		));
+
+		TimestampPallet::set_timestamp((GENESIS_TIME + 600) * 1_000);
+		Bridge::on_initialize(1);
+		assert!(!processed(transaction_hash));
+
+		assert_ok!(Bridge::set_bridge_mode(RuntimeOrigin::root(), BridgeMode::default()));
+		Bridge::on_initialize(2);
+		assert!(processed(transaction_hash));
+	})
+}
+
+#[test]
+fn report_reserve_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer()));
+		let who = create_account(b"6490B68F1116BFE87DDC");
+		assert_ok!(<Test as Config>::Assets::mint_into(TokenAssetId::get(), &who, token(1000)));
+
+		assert_noop!(
+			Bridge::report_reserve(RuntimeOrigin::signed(who), jur_currency(), 1, token(1000)),
+			Error::<Test>::NotPermitted
+		);
+		assert_noop!(
+			Bridge::report_reserve(RuntimeOrigin::signed(relayer()), currency(b"USD"), 1, 0),
+			Error::<Test>::UnknownCurrency
+		);
+		assert_ok!(Bridge::report_reserve(
+			RuntimeOrigin::signed(relayer()),
+			jur_currency(),
+			5,
+			token(1200)
+		));
+		assert_eq!(
+			Bridge::reserve_report(jur_currency()),
+			Some(ReserveReport {
+				relayer: relayer(),
+				ledger_index: 5,
+				reserve: token(1200),
+				supply: token(1000),
+				reported_at: 1,
+			})
+		);
+		System::assert_last_event(RuntimeEvent::Bridge(Event::ReserveReported(
+			jur_currency(),
+			5,
+			token(1200),
+			token(1000),
+		)));
+
+		// Reports must follow the ledger
+		assert_noop!(
+			Bridge::report_reserve(RuntimeOrigin::signed(relayer()), jur_currency(), 5, token(1)),
+			Error::<Test>::StaleReserveReport
+		);
+	})
+}
+
+#[test]
+fn reserve_shortfall_pauses_processing() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer()));
+		let who = create_account(b"6490B68F1116BFE87DDC");
+		assert_ok!(<Test as Config>::Assets::mint_into(TokenAssetId::get(), &who, token(1000)));
+
+		// Within `ReserveTolerance` of the supply
+		assert_ok!(Bridge::report_reserve(
+			RuntimeOrigin::signed(relayer()),
+			jur_currency(),
+			1,
+			token(990)
+		));
+		assert_eq!(Bridge::bridge_mode(), BridgeMode::default());
+
+		assert_ok!(Bridge::report_reserve(
+			RuntimeOrigin::signed(relayer()),
+			jur_currency(),
+			2,
+			token(989)
+		));
+		let mode = BridgeMode { inbound_processing_paused: true, ..Default::default() };
+		assert_eq!(Bridge::bridge_mode(), mode);
+		System::assert_has_event(RuntimeEvent::Bridge(Event::ReserveShortfall(
+			jur_currency(),
+			TokenAssetId::get(),
+			token(989),
+			token(1000),
+		)));
+		System::assert_last_event(RuntimeEvent::Bridge(Event::BridgeModeSet(mode)));
+
+		// Processing stays paused until resumed by `PauseOrigin`
+		let transaction_hash = submit_transaction(1);
+		let process_block = 1 + TxChallengePeriod::get() as u64;
+		System::set_block_number(process_block);
+		Bridge::on_initialize(process_block);
+		assert!(!processed(transaction_hash));
+	})
+}
+
+#[test]
+fn report_reserve_requires_stake() {
+	new_test_ext().execute_with(|| {
+		Bridge::initialize_relayer(&vec![relayer()]);
+
+		assert_noop!(
+			Bridge::report_reserve(RuntimeOrigin::signed(relayer()), jur_currency(), 1, 0),
+			Error::<Test>::InsufficientStake
+		);
+	})
+}
+
+#[test]
+fn reserve_reports_require_quorum() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let relayer_1 = create_account(b"6490B68F1116BFE87DD1");
+		let relayer_2 = create_account(b"6490B68F1116BFE87DD2");
+		let relayer_3 = create_account(b"6490B68F1116BFE87DD3");
+		for relayer in [relayer_1, relayer_2, relayer_3] {
+			assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer));
+		}
+		assert_ok!(Bridge::set_relayer_threshold(RuntimeOrigin::root(), 2));
+		let who = create_account(b"6490B68F1116BFE87DDC");
+		assert_ok!(<Test as Config>::Assets::mint_into(TokenAssetId::get(), &who, token(1000)));
+
+		// A single relayer can not pause processing
+		assert_ok!(Bridge::report_reserve(RuntimeOrigin::signed(relayer_1), jur_currency(), 5, 0));
+		System::assert_last_event(RuntimeEvent::Bridge(Event::ReserveAttested(
+			jur_currency(),
+			5,
+			0,
+			relayer_1,
+		)));
+		assert_eq!(Bridge::reserve_report(jur_currency()), None);
+		assert_eq!(Bridge::bridge_mode(), BridgeMode::default());
+
+		// Reports of other balances or ledger indexes do not count
+		assert_ok!(Bridge::report_reserve(
+			RuntimeOrigin::signed(relayer_2),
+			jur_currency(),
+			5,
+			token(1000)
+		));
+		assert_ok!(Bridge::report_reserve(RuntimeOrigin::signed(relayer_3), jur_currency(), 6, 0));
+		assert_eq!(Bridge::reserve_report(jur_currency()), None);
+
+		// Neither do reports of removed relayers
+		assert_ok!(Bridge::remove_relayer(RuntimeOrigin::root(), relayer_1));
+		assert_ok!(Bridge::report_reserve(RuntimeOrigin::signed(relayer_3), jur_currency(), 5, 0));
+		assert_eq!(Bridge::reserve_report(jur_currency()), None);
+
+		assert_ok!(Bridge::report_reserve(RuntimeOrigin::signed(relayer_2), jur_currency(), 5, 0));
+		assert_eq!(
+			Bridge::reserve_report(jur_currency()).map(|report| report.relayer),
+			Some(relayer_2)
+		);
+		assert_eq!(ReserveAttestations::<Test>::iter_prefix(jur_currency()).count(), 0);
+		assert!(Bridge::bridge_mode().inbound_processing_paused);
+	})
+}
+
+#[test]
+fn reserve_reports_of_inactive_relayers_do_not_fill_the_limit() {
+	new_test_ext().execute_with(|| {
+		MaxRelayers::set(2);
+		let relayer_1 = create_account(b"6490B68F1116BFE87DD1");
+		let relayer_2 = create_account(b"6490B68F1116BFE87DD2");
+		let relayer_3 = create_account(b"6490B68F1116BFE87DD3");
+		let relayer_4 = create_account(b"6490B68F1116BFE87DDE");
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer_1));
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer_2));
+		assert_ok!(Bridge::set_relayer_threshold(RuntimeOrigin::root(), 2));
+
+		// Junk reports of two relayers removed since fill the limit
+		assert_ok!(Bridge::report_reserve(RuntimeOrigin::signed(relayer_1), jur_currency(), 5, 1));
+		assert_ok!(Bridge::report_reserve(RuntimeOrigin::signed(relayer_2), jur_currency(), 6, 2));
+		assert_ok!(Bridge::remove_relayer(RuntimeOrigin::root(), relayer_1));
+		assert_ok!(Bridge::remove_relayer(RuntimeOrigin::root(), relayer_2));
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer_3));
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer_4));
+
+		// They are dropped instead of blocking the active relayers
+		assert_ok!(Bridge::report_reserve(RuntimeOrigin::signed(relayer_3), jur_currency(), 7, 0));
+		assert_eq!(Bridge::reserve_attestation(jur_currency(), relayer_1), None);
+		assert_eq!(Bridge::reserve_attestation(jur_currency(), relayer_2), None);
+		assert_ok!(Bridge::report_reserve(RuntimeOrigin::signed(relayer_4), jur_currency(), 7, 0));
+		assert_eq!(
+			Bridge::reserve_report(jur_currency()).map(|report| report.ledger_index),
+			Some(7)
+		);
+		assert_eq!(ReserveAttestations::<Test>::iter_prefix(jur_currency()).count(), 0);
+	})
+}
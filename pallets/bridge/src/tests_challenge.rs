@@ -0,0 +1,475 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::*;
+use sp_core::H160;
+
+fn challenger() -> AccountId {
+	create_account(b"6490B68F1116BFE87DDE")
+}
+
+fn submit_transaction(transaction_hash: TxHash) {
+	Bridge::initialize_relayer(&vec![relayer()]);
+	submit_payment(transaction_hash, token(1000), H160::from_low_u64_be(555));
+}
+
+fn challenge(transaction_hash: TxHash) {
+	assert_ok!(Bridge::submit_challenge(
+		RuntimeOrigin::signed(challenger()),
+		transaction_hash,
+		ChallengeReason::Other,
+		None
+	));
+}
+
+#[test]
+fn submit_challenge_reserves_bond() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		submit_transaction(transaction_hash);
+
+		assert_ok!(Bridge::submit_challenge(
+			RuntimeOrigin::signed(challenger()),
+			transaction_hash,
+			ChallengeReason::AmountMismatch,
+			Some(b"ledger amount 10".to_vec())
+		));
+		assert_eq!(Balances::reserved_balance(challenger()), ChallengeBond::get());
+		assert_eq!(
+			Bridge::challenge_transaction_list(transaction_hash),
+			Some(Challenge {
+				challenger: challenger(),
+				bond: ChallengeBond::get(),
+				reason: ChallengeReason::AmountMismatch,
+				evidence: Some(b"ledger amount 10".to_vec().try_into().unwrap()),
+				expires_at: 1 + ChallengeResolutionPeriod::get() as u64,
+			})
+		);
+		System::assert_last_event(RuntimeEvent::Bridge(Event::ChallengeSubmitted(
+			transaction_hash,
+			challenger(),
+			ChallengeBond::get(),
+			ChallengeReason::AmountMismatch,
+		)));
+
+		assert_noop!(
+			Bridge::submit_challenge(
+				RuntimeOrigin::signed(relayer()),
+				transaction_hash,
+				ChallengeReason::Other,
+				None
+			),
+			Error::<Test>::AlreadyChallenged
+		);
+	})
+}
+
+#[test]
+fn submit_challenge_requires_bond() {
+	new_test_ext().execute_with(|| {
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		let poor_challenger = create_account(b"6490B68F1116BFE87DDF");
+		submit_transaction(transaction_hash);
+
+		assert!(Bridge::submit_challenge(
+			RuntimeOrigin::signed(poor_challenger),
+			transaction_hash,
+			ChallengeReason::Other,
+			None
+		)
+		.is_err());
+		assert!(Bridge::challenge_transaction_list(transaction_hash).is_none());
+	})
+}
+
+#[test]
+fn failed_challenge_slashes_bond_to_relayer() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		submit_transaction(transaction_hash);
+		challenge(transaction_hash);
+
+		assert_ok!(Bridge::failed_challenge(RuntimeOrigin::root(), transaction_hash));
+		assert_eq!(Balances::reserved_balance(challenger()), 0);
+		assert_eq!(Balances::free_balance(challenger()), INITIAL_BALANCE - ChallengeBond::get());
+		assert_eq!(Balances::free_balance(relayer()), INITIAL_BALANCE + ChallengeBond::get());
+		System::assert_last_event(RuntimeEvent::Bridge(Event::ChallengeRejected(
+			transaction_hash,
+			challenger(),
+			relayer(),
+			ChallengeBond::get(),
+		)));
+
+		assert_noop!(
+			Bridge::failed_challenge(RuntimeOrigin::root(), transaction_hash),
+			Error::<Test>::ChallengeNotFound
+		);
+	})
+}
+
+#[test]
+fn success_challenge_returns_bond_with_reward() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		Balances::make_free_balance_be(&Bridge::account_id(), INITIAL_BALANCE);
+		submit_transaction(transaction_hash);
+		challenge(transaction_hash);
+
+		assert_ok!(Bridge::success_challenge(RuntimeOrigin::root(), transaction_hash));
+		assert_eq!(Balances::reserved_balance(challenger()), 0);
+		assert_eq!(Balances::free_balance(challenger()), INITIAL_BALANCE + ChallengeReward::get());
+		assert!(Bridge::challenge_transaction_list(transaction_hash).is_none());
+		System::assert_last_event(RuntimeEvent::Bridge(Event::ChallengeAccepted(
+			transaction_hash,
+			challenger(),
+			ChallengeBond::get(),
+			ChallengeReward::get(),
+		)));
+	})
+}
+
+#[test]
+fn success_challenge_reward_limited_by_bridge_funds() {
+	new_test_ext().execute_with(|| {
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		submit_transaction(transaction_hash);
+		challenge(transaction_hash);
+
+		assert_ok!(Bridge::success_challenge(RuntimeOrigin::root(), transaction_hash));
+		assert_eq!(Balances::free_balance(challenger()), INITIAL_BALANCE);
+	})
+}
+
+#[test]
+fn success_challenge_slashes_relayer_stake() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer()));
+		// Unbonding stake is still slashable
+		assert_ok!(Bridge::register_relayer(RuntimeOrigin::signed(relayer()), 1_000));
+		assert_ok!(Bridge::unbond_relayer(RuntimeOrigin::signed(relayer()), 1_000));
+		submit_transaction(transaction_hash);
+		challenge(transaction_hash);
+
+		assert_ok!(Bridge::success_challenge(RuntimeOrigin::root(), transaction_hash));
+		let slashed = RelayerSlash::get() * (RelayerMinStake::get() + 1_000);
+		System::assert_has_event(RuntimeEvent::Bridge(Event::RelayerSlashed(
+			relayer(),
+			transaction_hash,
+			slashed,
+		)));
+		assert_eq!(Balances::reserved_balance(relayer()), RelayerMinStake::get() + 1_000 - slashed);
+		// The slashed stake funds the challenger reward
+		assert_eq!(Balances::free_balance(Bridge::account_id()), slashed - ChallengeReward::get());
+		assert_eq!(Bridge::get_relayer(relayer()), Some(false));
+		System::assert_has_event(RuntimeEvent::Bridge(Event::RelayerDeactivated(relayer())));
+	})
+}
+
+#[test]
+fn success_challenge_refunds_unused_weight() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		submit_transaction(transaction_hash);
+		challenge(transaction_hash);
+
+		let info = Bridge::success_challenge(RuntimeOrigin::root(), transaction_hash).unwrap();
+		assert_eq!(info.actual_weight, Some(<() as WeightInfo>::success_challenge(1)));
+	})
+}
+
+#[test]
+fn transaction_status_follows_lifecycle() {
+	new_test_ext().execute_with(|| {
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		let process_block = TxChallengePeriod::get() as u64;
+		assert_eq!(Bridge::transaction_status(transaction_hash), None);
+		submit_transaction(transaction_hash);
+		assert_eq!(
+			Bridge::transaction_status(transaction_hash),
+			Some(TxStatus::Pending(ProcessAt::Block(process_block)))
+		);
+
+		challenge(transaction_hash);
+		assert_eq!(
+			Bridge::transaction_status(transaction_hash),
+			Some(TxStatus::Challenged(ProcessAt::Block(process_block)))
+		);
+
+		// Resolved before the process block, the transaction keeps its slot
+		assert_ok!(Bridge::failed_challenge(RuntimeOrigin::root(), transaction_hash));
+		assert_eq!(
+			Bridge::transaction_status(transaction_hash),
+			Some(TxStatus::Pending(ProcessAt::Block(process_block)))
+		);
+		assert_eq!(Bridge::process_transaction(process_block).unwrap(), vec![transaction_hash]);
+
+		System::set_block_number(process_block);
+		Bridge::on_initialize(process_block);
+		assert_eq!(Bridge::transaction_status(transaction_hash), Some(TxStatus::Settled));
+		assert_noop!(
+			Bridge::submit_challenge(
+				RuntimeOrigin::signed(challenger()),
+				transaction_hash,
+				ChallengeReason::Other,
+				None
+			),
+			Error::<Test>::ChallengePeriodOver
+		);
+	})
+}
+
+#[test]
+fn success_challenge_rejects_transaction() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		let process_block = 1 + TxChallengePeriod::get() as u64;
+		submit_transaction(transaction_hash);
+		challenge(transaction_hash);
+
+		assert_ok!(Bridge::success_challenge(RuntimeOrigin::root(), transaction_hash));
+		assert_eq!(Bridge::transaction_status(transaction_hash), Some(TxStatus::Rejected));
+		assert!(Bridge::process_transaction(process_block).is_none());
+		assert!(Bridge::process_transaction_details(transaction_hash).is_none());
+		assert!(Bridge::challenge_transaction_list(transaction_hash).is_none());
+		System::assert_has_event(RuntimeEvent::Bridge(Event::TransactionRejected(
+			1,
+			transaction_hash,
+		)));
+		assert_noop!(
+			Bridge::failed_challenge(RuntimeOrigin::root(), transaction_hash),
+			Error::<Test>::ChallengeNotFound
+		);
+
+		// The hash can be submitted again with the correct data
+		submit_transaction(transaction_hash);
+		assert_eq!(
+			Bridge::transaction_status(transaction_hash),
+			Some(TxStatus::Pending(ProcessAt::Block(process_block)))
+		);
+	})
+}
+
+#[test]
+fn submit_challenge_validation() {
+	new_test_ext().execute_with(|| {
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		assert_noop!(
+			Bridge::submit_challenge(
+				RuntimeOrigin::signed(challenger()),
+				transaction_hash,
+				ChallengeReason::NotOnLedger,
+				None
+			),
+			Error::<Test>::UnknownTransaction
+		);
+
+		submit_transaction(transaction_hash);
+		assert_noop!(
+			Bridge::submit_challenge(
+				RuntimeOrigin::signed(challenger()),
+				transaction_hash,
+				ChallengeReason::Other,
+				Some(vec![0; MaxEvidenceLength::get() as usize + 1])
+			),
+			Error::<Test>::EvidenceTooLong
+		);
+
+		// The process block is the last one challenges are accepted before
+		System::set_block_number(TxChallengePeriod::get() as u64);
+		assert_noop!(
+			Bridge::submit_challenge(
+				RuntimeOrigin::signed(challenger()),
+				transaction_hash,
+				ChallengeReason::Other,
+				None
+			),
+			Error::<Test>::ChallengePeriodOver
+		);
+	})
+}
+
+#[test]
+fn expired_challenge_releases_transaction() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		submit_transaction(transaction_hash);
+		challenge(transaction_hash);
+
+		let expires_at = 1 + ChallengeResolutionPeriod::get() as u64;
+		System::set_block_number(expires_at);
+		Bridge::on_initialize(expires_at);
+
+		assert_eq!(Balances::reserved_balance(challenger()), 0);
+		assert_eq!(Balances::free_balance(challenger()), INITIAL_BALANCE);
+		assert!(Bridge::challenge_transaction_list(transaction_hash).is_none());
+		assert!(Bridge::challenge_expiries(expires_at).is_none());
+		System::assert_last_event(RuntimeEvent::Bridge(Event::ChallengeExpired(
+			transaction_hash,
+			challenger(),
+			ChallengeBond::get(),
+			ChallengeExpiry::Release,
+		)));
+		// The challenge period is not over yet, the transaction keeps its slot
+		let process_block = 1 + TxChallengePeriod::get() as u64;
+		assert_eq!(
+			Bridge::transaction_status(transaction_hash),
+			Some(TxStatus::Pending(ProcessAt::Block(process_block)))
+		);
+	})
+}
+
+#[test]
+fn expired_challenge_rejects_transaction() {
+	new_test_ext().execute_with(|| {
+		ChallengeExpiryPolicy::set(ChallengeExpiry::Reject);
+		System::set_block_number(1);
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		submit_transaction(transaction_hash);
+		challenge(transaction_hash);
+
+		let expires_at = 1 + ChallengeResolutionPeriod::get() as u64;
+		System::set_block_number(expires_at);
+		Bridge::on_initialize(expires_at);
+
+		assert_eq!(Balances::free_balance(challenger()), INITIAL_BALANCE);
+		assert_eq!(Bridge::transaction_status(transaction_hash), Some(TxStatus::Rejected));
+		assert!(Bridge::process_transaction_details(transaction_hash).is_none());
+		assert!(Bridge::process_transaction(1 + TxChallengePeriod::get() as u64).is_none());
+		// Nobody proved the relayer wrong
+		assert_eq!(Balances::free_balance(relayer()), INITIAL_BALANCE);
+		System::assert_has_event(RuntimeEvent::Bridge(Event::TransactionRejected(
+			1,
+			transaction_hash,
+		)));
+		System::assert_last_event(RuntimeEvent::Bridge(Event::ChallengeExpired(
+			transaction_hash,
+			challenger(),
+			ChallengeBond::get(),
+			ChallengeExpiry::Reject,
+		)));
+	})
+}
+
+#[test]
+fn resolved_challenge_does_not_expire() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		submit_transaction(transaction_hash);
+		challenge(transaction_hash);
+		assert_ok!(Bridge::failed_challenge(RuntimeOrigin::root(), transaction_hash));
+
+		let expires_at = 1 + ChallengeResolutionPeriod::get() as u64;
+		System::reset_events();
+		Bridge::on_initialize(expires_at);
+		assert!(System::events().is_empty());
+	})
+}
+
+#[test]
+fn submit_challenge_fails_when_expiry_block_is_full() {
+	new_test_ext().execute_with(|| {
+		MaxTransactionsPerBlock::set(1);
+		let first = TxHash::from_low_u64_be(1);
+		let second = TxHash::from_low_u64_be(2);
+		submit_transaction(first);
+		System::set_block_number(1);
+		submit_transaction(second);
+		challenge(first);
+
+		assert_noop!(
+			Bridge::submit_challenge(
+				RuntimeOrigin::signed(challenger()),
+				second,
+				ChallengeReason::Other,
+				None
+			),
+			Error::<Test>::TooManyChallenges
+		);
+	})
+}
+
+#[test]
+fn wall_clock_challenge_period_ends_by_time() {
+	new_test_ext().execute_with(|| {
+		WallClockChallengePeriod::set(Some(600));
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		submit_transaction(transaction_hash);
+
+		// Still open however many blocks passed
+		System::set_block_number(TxChallengePeriod::get() as u64 * 2);
+		TimestampPallet::set_timestamp((GENESIS_TIME + 599) * 1_000);
+		challenge(transaction_hash);
+		assert_eq!(
+			Bridge::transaction_status(transaction_hash),
+			Some(TxStatus::Challenged(ProcessAt::Time(GENESIS_TIME + 600)))
+		);
+		assert_ok!(Bridge::failed_challenge(RuntimeOrigin::root(), transaction_hash));
+
+		TimestampPallet::set_timestamp((GENESIS_TIME + 600) * 1_000);
+		assert_noop!(
+			Bridge::submit_challenge(
+				RuntimeOrigin::signed(challenger()),
+				transaction_hash,
+				ChallengeReason::Other,
+				None
+			),
+			Error::<Test>::ChallengePeriodOver
+		);
+	})
+}
+
+#[test]
+fn wall_clock_rejected_transaction_is_unscheduled() {
+	new_test_ext().execute_with(|| {
+		WallClockChallengePeriod::set(Some(600));
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		submit_transaction(transaction_hash);
+		challenge(transaction_hash);
+		assert_ok!(Bridge::success_challenge(RuntimeOrigin::root(), transaction_hash));
+
+		assert_eq!(Bridge::transaction_status(transaction_hash), Some(TxStatus::Rejected));
+		assert!(Bridge::timed_process_transaction(GENESIS_TIME + 600).is_none());
+	})
+}
+
+#[test]
+fn queries_list_pending_transactions_and_open_challenges() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let pending = TxHash::from_low_u64_be(123);
+		let challenged = TxHash::from_low_u64_be(124);
+		submit_transaction(pending);
+		submit_transaction(challenged);
+		assert_ok!(Bridge::submit_challenge(
+			RuntimeOrigin::signed(challenger()),
+			challenged,
+			ChallengeReason::AmountMismatch,
+			Some(b"ledger amount 10".to_vec())
+		));
+
+		let process_at = ProcessAt::Block(1 + TxChallengePeriod::get() as u64);
+		assert_eq!(Bridge::pending_transactions(), vec![(pending, process_at)]);
+		assert_eq!(
+			Bridge::open_challenges(),
+			vec![(
+				challenged,
+				Challenge {
+					challenger: challenger(),
+					bond: ChallengeBond::get(),
+					reason: ChallengeReason::AmountMismatch,
+					evidence: Some(b"ledger amount 10".to_vec()),
+					expires_at: 1 + ChallengeResolutionPeriod::get() as u64,
+				}
+			)]
+		);
+		assert_eq!(Bridge::relayers(), vec![relayer()]);
+	})
+}
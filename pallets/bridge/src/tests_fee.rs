@@ -0,0 +1,154 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::*;
+use sp_core::H160;
+
+fn destination() -> AccountId {
+	create_account(b"6490B68F1116BFE87DDC")
+}
+
+/// Submit a payment of `amount` to `destination` and process it once its challenge period ends
+fn mint_payment(amount: Balance) -> TxHash {
+	let transaction_hash = TxHash::from_low_u64_be(1);
+	Bridge::initialize_relayer(&vec![relayer()]);
+	submit_payment(transaction_hash, amount, H160::from_slice(b"6490B68F1116BFE87DDC"));
+	let process_block = TxChallengePeriod::get() as u64;
+	System::set_block_number(process_block);
+	Bridge::on_initialize(process_block);
+	transaction_hash
+}
+
+fn fee_schedule(flat: Balance, basis_points: u32) -> FeeSchedule {
+	FeeSchedule { flat, basis_points, relayer_share: Perbill::from_percent(60) }
+}
+
+#[test]
+fn set_fee_schedule_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let schedule = fee_schedule(token(1), 100);
+
+		assert_noop!(
+			Bridge::set_fee_schedule(RuntimeOrigin::signed(relayer()), schedule),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_noop!(
+			Bridge::set_fee_schedule(RuntimeOrigin::root(), fee_schedule(token(1), 10_001)),
+			Error::<Test>::InvalidFeeSchedule
+		);
+
+		assert_ok!(Bridge::set_fee_schedule(RuntimeOrigin::root(), schedule));
+		assert_eq!(Bridge::fee_schedule(), schedule);
+		System::assert_last_event(RuntimeEvent::Bridge(Event::FeeScheduleSet(schedule)));
+
+		assert_ok!(Bridge::set_minimum_fee(RuntimeOrigin::root(), 2, Some(token(5))));
+		assert_eq!(Bridge::minimum_fee(2), Some(token(5)));
+		System::assert_last_event(RuntimeEvent::Bridge(Event::MinimumFeeSet(2, Some(token(5)))));
+		assert_ok!(Bridge::set_minimum_fee(RuntimeOrigin::root(), 2, None));
+		assert_eq!(Bridge::minimum_fee(2), None);
+	})
+}
+
+#[test]
+fn fee_is_split_between_relayer_and_treasury() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bridge::set_fee_schedule(RuntimeOrigin::root(), fee_schedule(token(1), 100)));
+
+		let transaction_hash = mint_payment(token(1000));
+
+		// 1 token flat and 1% of the payment
+		assert_eq!(token_balance_of(destination()), token(989));
+		assert_eq!(token_balance_of(relayer()), 6_600_000);
+		assert_eq!(token_balance_of(TreasuryAccount::get()), 4_400_000);
+		assert_eq!(Bridge::relayer_earnings(relayer(), TokenAssetId::get()), 6_600_000);
+		System::assert_has_event(RuntimeEvent::Bridge(Event::FeePaid(
+			transaction_hash,
+			TokenAssetId::get(),
+			relayer(),
+			6_600_000,
+			4_400_000,
+		)));
+		// The whole payment is still backed by the reserve
+		assert_eq!(<Test as Config>::Assets::total_issuance(TokenAssetId::get()), token(1000));
+	})
+}
+
+#[test]
+fn minimum_fee_applies() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bridge::set_fee_schedule(RuntimeOrigin::root(), fee_schedule(token(1), 100)));
+		assert_ok!(Bridge::set_minimum_fee(
+			RuntimeOrigin::root(),
+			TokenAssetId::get(),
+			Some(token(20))
+		));
+
+		mint_payment(token(1000));
+
+		assert_eq!(token_balance_of(destination()), token(980));
+		assert_eq!(Bridge::relayer_earnings(relayer(), TokenAssetId::get()), token(12));
+	})
+}
+
+#[test]
+fn fee_is_capped_at_payment() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bridge::set_fee_schedule(RuntimeOrigin::root(), fee_schedule(token(2000), 0)));
+
+		let transaction_hash = mint_payment(token(1000));
+
+		assert_eq!(Bridge::transaction_status(transaction_hash), Some(TxStatus::Settled));
+		assert_eq!(token_balance_of(destination()), 0);
+		assert_eq!(token_balance_of(relayer()), token(600));
+		assert_eq!(token_balance_of(TreasuryAccount::get()), token(400));
+	})
+}
+
+#[test]
+fn no_fee_by_default() {
+	new_test_ext().execute_with(|| {
+		mint_payment(token(1000));
+
+		assert_eq!(token_balance_of(destination()), token(1000));
+		assert_eq!(Bridge::relayer_earnings(relayer(), TokenAssetId::get()), 0);
+		assert!(!System::events()
+			.iter()
+			.any(|record| matches!(record.event, RuntimeEvent::Bridge(Event::FeePaid(..)))));
+	})
+}
+
+#[test]
+fn fee_below_minimum_balance_is_left_with_destination() {
+	new_test_ext().execute_with(|| {
+		let usd = currency(b"USD");
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 3, relayer(), true, token(1)));
+		assert_ok!(Bridge::register_currency(RuntimeOrigin::root(), usd, 3));
+		assert_ok!(Bridge::set_fee_schedule(RuntimeOrigin::root(), fee_schedule(1_000, 0)));
+		Bridge::initialize_relayer(&vec![relayer()]);
+
+		let transaction_hash = TxHash::from_low_u64_be(1);
+		assert_ok!(Bridge::submit_transaction(
+			RuntimeOrigin::signed(relayer()),
+			1,
+			transaction_hash,
+			TxData::Payment {
+				amount: token(10),
+				address: H160::from_slice(b"6490B68F1116BFE87DDC"),
+				currency: usd,
+			},
+			1234
+		));
+		let process_block = TxChallengePeriod::get() as u64;
+		System::set_block_number(process_block);
+		Bridge::on_initialize(process_block);
+
+		// Neither share reaches the minimum balance of the fresh asset
+		assert_eq!(Bridge::transaction_status(transaction_hash), Some(TxStatus::Settled));
+		assert_eq!(<Test as Config>::Assets::balance(3, &destination()), token(10));
+		assert_eq!(<Test as Config>::Assets::balance(3, &TreasuryAccount::get()), 0);
+		assert_eq!(Bridge::relayer_earnings(relayer(), 3), 0);
+		assert!(!System::events()
+			.iter()
+			.any(|record| matches!(record.event, RuntimeEvent::Bridge(Event::FeePaid(..)))));
+	})
+}
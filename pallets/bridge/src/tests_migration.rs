@@ -0,0 +1,110 @@
+use super::*;
+use crate::migrations::v1::{MigrateToV1, OldTransaction, OldTxData};
+use frame_support::{
+	storage::unhashed,
+	traits::{OnRuntimeUpgrade, StorageVersion},
+};
+use mock::*;
+use sp_core::H160;
+
+pub struct LegacyCurrency;
+
+impl Get<ExternalCurrency> for LegacyCurrency {
+	fn get() -> ExternalCurrency {
+		jur_currency()
+	}
+}
+
+/// Store a payment submitted by `relayer()` in the format of storage version 0
+fn put_old_transaction(transaction_hash: TxHash) {
+	let old = (
+		1 as LedgerIndex,
+		OldTransaction {
+			transaction_hash,
+			transaction: OldTxData::Payment {
+				amount: token(1000),
+				address: H160::from_low_u64_be(555),
+			},
+			timestamp: 1234,
+		},
+		relayer(),
+	);
+	unhashed::put(&ProcessTransactionDetails::<Test>::hashed_key_for(transaction_hash), &old);
+}
+
+#[test]
+fn migrates_storage_from_version_0() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+		MaxTransactionsPerBlock::set(1);
+		StorageVersion::new(0).put::<Bridge>();
+		CurrencyRegistry::<Test>::remove(jur_currency());
+		Relayer::<Test>::insert(relayer(), true);
+		let challenger = create_account(b"6490B68F1116BFE87DDE");
+		let (pending, challenged, settled, rejected) = (
+			TxHash::from_low_u64_be(1),
+			TxHash::from_low_u64_be(2),
+			TxHash::from_low_u64_be(3),
+			TxHash::from_low_u64_be(4),
+		);
+		for transaction_hash in [pending, challenged, settled] {
+			put_old_transaction(transaction_hash);
+		}
+		unhashed::put(&ProcessTransaction::<Test>::hashed_key_for(50), &vec![pending, challenged]);
+		unhashed::put(&SettledTransactionDetails::<Test>::hashed_key_for(1_000), &vec![settled]);
+		for transaction_hash in [challenged, rejected] {
+			unhashed::put(
+				&ChallengeTransactionList::<Test>::hashed_key_for(transaction_hash),
+				&challenger,
+			);
+		}
+
+		MigrateToV1::<Test, LegacyCurrency>::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get::<Bridge>(), 1);
+		assert_eq!(Bridge::currency_registry(jur_currency()), Some(TokenAssetId::get()));
+		assert_eq!(Bridge::relayer_count(), 1);
+		let (_, transaction, _) = Bridge::process_transaction_details(pending).unwrap();
+		assert_eq!(
+			transaction.transaction,
+			TxData::Payment {
+				amount: token(1000),
+				address: H160::from_low_u64_be(555),
+				currency: jur_currency(),
+			}
+		);
+		// The second transaction of block 50 moves to the next block with room left
+		assert_eq!(Bridge::process_transaction(50).unwrap().to_vec(), vec![pending]);
+		assert_eq!(Bridge::process_transaction(51).unwrap().to_vec(), vec![challenged]);
+		assert_eq!(
+			Bridge::transaction_status(pending),
+			Some(TxStatus::Pending(ProcessAt::Block(50)))
+		);
+		assert_eq!(
+			Bridge::transaction_status(challenged),
+			Some(TxStatus::Challenged(ProcessAt::Block(51)))
+		);
+		let expires_at = 10 + ChallengeResolutionPeriod::get() as u64;
+		assert_eq!(
+			Bridge::challenge_transaction_list(challenged),
+			Some(Challenge {
+				challenger,
+				bond: 0,
+				reason: ChallengeReason::Other,
+				evidence: None,
+				expires_at,
+			})
+		);
+		assert_eq!(Bridge::challenge_expiries(expires_at).unwrap().to_vec(), vec![challenged]);
+		assert_eq!(Bridge::transaction_status(settled), Some(TxStatus::Settled));
+		assert_eq!(Bridge::settled_transaction_details(1_000).unwrap().to_vec(), vec![settled]);
+		// Challenged transactions already removed were proven invalid
+		assert_eq!(Bridge::transaction_status(rejected), Some(TxStatus::Rejected));
+		assert_eq!(Bridge::challenge_transaction_list(rejected), None);
+
+		// Only runs once
+		CurrencyRegistry::<Test>::remove(jur_currency());
+		MigrateToV1::<Test, LegacyCurrency>::on_runtime_upgrade();
+		assert_eq!(Bridge::currency_registry(jur_currency()), None);
+	})
+}
@@ -0,0 +1,498 @@
+use super::*;
+use crate::{
+	crypto::{RelayerAuthId, KEY_TYPE},
+	offchain::{
+		decode_address, parse_value, OffchainError, DOOR_ACCOUNT_KEY, LAST_LEDGER_KEY,
+		LEDGER_ENDPOINT_KEY, WATCHED_KEY, WATCHER_KEY,
+	},
+};
+use codec::Decode;
+use frame_support::assert_ok;
+use mock::*;
+use parking_lot::RwLock;
+use primitives::signature::{BridgeSignature, BridgeSigner};
+use sp_core::{
+	hexdisplay::HexDisplay,
+	offchain::{
+		testing::{
+			OffchainState, PendingRequest, PoolState, TestOffchainExt, TestTransactionPoolExt,
+		},
+		OffchainDbExt, OffchainWorkerExt, StorageKind, TransactionPoolExt,
+	},
+	H160,
+};
+use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStore};
+use sp_runtime::{
+	offchain::storage::StorageValueRef,
+	traits::{IdentifyAccount, Verify},
+};
+use std::sync::Arc;
+
+const ENDPOINT: &str = "http://localhost:5005";
+const DOOR_ACCOUNT: &str = "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh";
+/// Issuer of `jur_currency`
+const JUR_ISSUER: &str = "rrrrrrrrrrrrrrrrrrrrBZbvji";
+const PHRASE: &str = "news slush supreme milk chapter athlete soap sausage put clutch what kitten";
+/// Ledger close time of the mocked ledgers
+const CLOSE_TIME: u64 = 700_000_000;
+
+/// Test externalities with a relayer key in the keystore and the offchain worker configured
+fn offchain_ext(
+) -> (sp_io::TestExternalities, Arc<RwLock<OffchainState>>, Arc<RwLock<PoolState>>, BridgeSigner) {
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let keystore = KeyStore::new();
+	let public = SyncCryptoStore::ecdsa_generate_new(
+		&keystore,
+		KEY_TYPE,
+		Some(&format!("{}/hunter1", PHRASE)),
+	)
+	.unwrap();
+	let signer = BridgeSigner::from(public);
+
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(Arc::new(keystore)));
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		Bridge::initialize_relayer(&vec![signer.clone().into_account()]);
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			LEDGER_ENDPOINT_KEY,
+			ENDPOINT.as_bytes(),
+		);
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			DOOR_ACCOUNT_KEY,
+			DOOR_ACCOUNT.as_bytes(),
+		);
+	});
+	(ext, offchain_state, pool_state, signer)
+}
+
+fn expect_rpc(state: &Arc<RwLock<OffchainState>>, body: &str, response: String) {
+	state.write().expect_request(PendingRequest {
+		method: "POST".into(),
+		uri: ENDPOINT.into(),
+		headers: vec![("Content-Type".into(), "application/json".into())],
+		body: body.as_bytes().to_vec(),
+		response: Some(response.into_bytes()),
+		sent: true,
+		..Default::default()
+	});
+}
+
+fn expect_validated_ledger(state: &Arc<RwLock<OffchainState>>, ledger_index: LedgerIndex) {
+	expect_rpc(
+		state,
+		r#"{"method":"ledger","params":[{"ledger_index":"validated"}]}"#,
+		format!(
+			r#"{{"result":{{"ledger_index":{},"validated":true,"status":"success"}}}}"#,
+			ledger_index
+		),
+	);
+}
+
+fn expect_ledger(
+	state: &Arc<RwLock<OffchainState>>,
+	ledger_index: LedgerIndex,
+	transactions: Vec<String>,
+) {
+	expect_rpc(
+		state,
+		&format!(
+			r#"{{"method":"ledger","params":[{{"ledger_index":{},"transactions":true,"expand":true}}]}}"#,
+			ledger_index
+		),
+		format!(
+			r#"{{"result":{{"ledger":{{"close_time":{},"ledger_index":"{}","transactions":[{}]}},"ledger_index":{},"validated":true,"status":"success"}}}}"#,
+			CLOSE_TIME,
+			ledger_index,
+			transactions.join(","),
+			ledger_index
+		),
+	);
+}
+
+/// Payment of JUR on the external ledger, delivering `delivered` out of 2000 sent
+fn payment_json(hash: u64, destination: &str, result: &str, delivered: &str) -> String {
+	let memo_data = HexDisplay::from(&H160::from_low_u64_be(555).as_bytes()).to_string();
+	format!(
+		r#"{{"Account":"{issuer}","Amount":{{"currency":"JUR","issuer":"{issuer}","value":"2000"}},"Destination":"{destination}","Memos":[{{"Memo":{{"MemoData":"{memo_data}"}}}}],"TransactionType":"Payment","hash":"{hash:064X}","metaData":{{"TransactionResult":"{result}","delivered_amount":{{"currency":"JUR","issuer":"{issuer}","value":"{delivered}"}}}}}}"#,
+		issuer = JUR_ISSUER,
+	)
+}
+
+fn submit_transaction_call(ledger_index: LedgerIndex, hash: u64, amount: Balance) -> RuntimeCall {
+	RuntimeCall::Bridge(crate::Call::submit_transaction {
+		ledger_index,
+		transaction_hash: TxHash::from_low_u64_be(hash),
+		transaction: TxData::Payment {
+			amount,
+			address: H160::from_low_u64_be(555),
+			currency: jur_currency(),
+		},
+		timestamp: CLOSE_TIME + 946_684_800,
+	})
+}
+
+/// Test externalities watching a payment of 1000 JUR submitted for ledger 100
+fn watcher_ext() -> (sp_io::TestExternalities, Arc<RwLock<OffchainState>>, Arc<RwLock<PoolState>>) {
+	let (mut ext, offchain, pool, signer) = offchain_ext();
+	ext.execute_with(|| {
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, WATCHER_KEY, b"1");
+		assert_ok!(Bridge::submit_transaction(
+			RuntimeOrigin::signed(signer.into_account()),
+			100,
+			TxHash::from_low_u64_be(1),
+			TxData::Payment {
+				amount: token(1000),
+				address: H160::from_low_u64_be(555),
+				currency: jur_currency(),
+			},
+			1234
+		));
+	});
+	(ext, offchain, pool)
+}
+
+fn expect_transaction_lookup(state: &Arc<RwLock<OffchainState>>, response: String) {
+	expect_rpc(
+		state,
+		&format!(
+			r#"{{"method":"tx","params":[{{"transaction":"{:064X}","binary":false,"min_ledger":100,"max_ledger":100}}]}}"#,
+			1
+		),
+		response,
+	);
+}
+
+/// Transaction looked up on the external ledger, with its destination account in its memo
+fn expect_transaction(
+	state: &Arc<RwLock<OffchainState>>,
+	ledger_index: LedgerIndex,
+	destination: u64,
+	delivered: &str,
+) {
+	let memo_data = HexDisplay::from(&H160::from_low_u64_be(destination).as_bytes()).to_string();
+	expect_transaction_lookup(
+		state,
+		format!(
+			r#"{{"result":{{"Account":"{issuer}","Amount":{{"currency":"JUR","issuer":"{issuer}","value":"{delivered}"}},"Destination":"{DOOR_ACCOUNT}","Memos":[{{"Memo":{{"MemoData":"{memo_data}"}}}}],"TransactionType":"Payment","hash":"{hash:064X}","ledger_index":{ledger_index},"meta":{{"TransactionResult":"tesSUCCESS","delivered_amount":{{"currency":"JUR","issuer":"{issuer}","value":"{delivered}"}}}},"validated":true,"status":"success"}}}}"#,
+			hash = 1,
+			issuer = JUR_ISSUER,
+		),
+	);
+}
+
+fn assert_challenged(pool: &Arc<RwLock<PoolState>>, reason: ChallengeReason, evidence: &str) {
+	let transactions = pool.read().transactions.clone();
+	assert_eq!(transactions.len(), 1);
+	let challenge = Extrinsic::decode(&mut &*transactions[0]).unwrap();
+	assert_eq!(challenge.signature, Some((0, ())));
+	assert_eq!(
+		challenge.call,
+		RuntimeCall::Bridge(crate::Call::submit_challenge {
+			transaction_hash: TxHash::from_low_u64_be(1),
+			reason,
+			evidence: Some(evidence.as_bytes().to_vec()),
+		})
+	);
+}
+
+#[test]
+fn relays_payments_to_door_account() {
+	let (mut ext, offchain, pool, _) = offchain_ext();
+	expect_validated_ledger(&offchain, 100);
+	expect_ledger(
+		&offchain,
+		100,
+		vec![
+			payment_json(1, DOOR_ACCOUNT, "tesSUCCESS", "1000"),
+			payment_json(2, JUR_ISSUER, "tesSUCCESS", "1000"),
+			payment_json(3, DOOR_ACCOUNT, "tecPATH_DRY", "0"),
+			payment_json(4, DOOR_ACCOUNT, "tesSUCCESS", "0.5"),
+		],
+	);
+
+	ext.execute_with(|| {
+		assert_ok!(Bridge::relay_ledgers(1));
+
+		let transactions = pool.read().transactions.clone();
+		assert_eq!(transactions.len(), 2);
+		let first = Extrinsic::decode(&mut &*transactions[0]).unwrap();
+		assert_eq!(first.signature, Some((0, ())));
+		// The amount delivered by a partial payment is relayed
+		assert_eq!(first.call, submit_transaction_call(100, 1, token(1000)));
+		let second = Extrinsic::decode(&mut &*transactions[1]).unwrap();
+		assert_eq!(second.signature, Some((1, ())));
+		assert_eq!(second.call, submit_transaction_call(100, 4, 500_000));
+		assert_eq!(
+			StorageValueRef::persistent(LAST_LEDGER_KEY).get::<LedgerIndex>(),
+			Ok(Some(100))
+		);
+	});
+}
+
+#[test]
+fn resumes_from_last_ledger() {
+	let (mut ext, offchain, pool, _) = offchain_ext();
+	expect_validated_ledger(&offchain, 100);
+	// At most `OffchainLedgersPerRun` ledgers per run
+	expect_ledger(&offchain, 98, vec![]);
+	expect_ledger(&offchain, 99, vec![payment_json(1, DOOR_ACCOUNT, "tesSUCCESS", "1000")]);
+
+	ext.execute_with(|| {
+		StorageValueRef::persistent(LAST_LEDGER_KEY).set(&97u64);
+		assert_ok!(Bridge::relay_ledgers(1));
+
+		assert_eq!(pool.read().transactions.len(), 1);
+		assert_eq!(StorageValueRef::persistent(LAST_LEDGER_KEY).get::<LedgerIndex>(), Ok(Some(99)));
+	});
+}
+
+#[test]
+fn skips_payments_failing_to_submit() {
+	let (mut ext, offchain, pool, _) = offchain_ext();
+	SignTransactions::set(false);
+	let last_ledger = || StorageValueRef::persistent(LAST_LEDGER_KEY).get::<LedgerIndex>();
+	// Retried on each run until it failed `MAX_SUBMIT_ATTEMPTS` times
+	for attempt in 1..=5 {
+		expect_validated_ledger(&offchain, 100);
+		expect_ledger(&offchain, 100, vec![payment_json(1, DOOR_ACCOUNT, "tesSUCCESS", "1000")]);
+		ext.execute_with(|| {
+			if attempt < 5 {
+				assert_eq!(Bridge::relay_ledgers(1), Err(OffchainError::SubmitFailed));
+				assert_eq!(last_ledger(), Ok(None));
+			} else {
+				assert_ok!(Bridge::relay_ledgers(1));
+				assert_eq!(last_ledger(), Ok(Some(100)));
+			}
+		});
+	}
+	assert!(pool.read().transactions.is_empty());
+}
+
+#[test]
+fn skips_attested_payments() {
+	let (mut ext, offchain, pool, signer) = offchain_ext();
+	expect_validated_ledger(&offchain, 100);
+	expect_ledger(&offchain, 100, vec![payment_json(1, DOOR_ACCOUNT, "tesSUCCESS", "1000")]);
+
+	ext.execute_with(|| {
+		assert_ok!(Bridge::submit_transaction(
+			RuntimeOrigin::signed(signer.into_account()),
+			100,
+			TxHash::from_low_u64_be(1),
+			TxData::Payment {
+				amount: token(1000),
+				address: H160::from_low_u64_be(555),
+				currency: jur_currency(),
+			},
+			1234
+		));
+		assert_ok!(Bridge::relay_ledgers(1));
+
+		assert!(pool.read().transactions.is_empty());
+		assert_eq!(
+			StorageValueRef::persistent(LAST_LEDGER_KEY).get::<LedgerIndex>(),
+			Ok(Some(100))
+		);
+	});
+}
+
+#[test]
+fn does_nothing_when_not_configured() {
+	let (mut ext, _, pool, _) = offchain_ext();
+	ext.execute_with(|| {
+		sp_io::offchain::local_storage_clear(StorageKind::PERSISTENT, LEDGER_ENDPOINT_KEY);
+		assert_ok!(Bridge::relay_ledgers(1));
+		assert!(pool.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn requires_relayer_key() {
+	let (mut ext, _, _, signer) = offchain_ext();
+	ext.execute_with(|| {
+		Relayer::<Test>::remove(signer.into_account());
+		assert_eq!(Bridge::relay_ledgers(1), Err(OffchainError::NoRelayerKey));
+	});
+}
+
+#[test]
+fn relayer_keys_sign_bridge_signatures() {
+	let (mut ext, _, _, signer) = offchain_ext();
+	ext.execute_with(|| {
+		let signature: BridgeSignature = RelayerAuthId::sign(b"payload", signer.clone()).unwrap();
+		assert!(signature.verify(&b"payload"[..], &signer.clone().into_account()));
+		assert!(RelayerAuthId::verify(b"payload", signer, signature));
+		assert!(RelayerAuthId::sign(b"payload", BridgeSigner::from([0u8; 20])).is_none());
+	});
+}
+
+#[test]
+fn decode_address_works() {
+	assert_eq!(
+		decode_address(DOOR_ACCOUNT.as_bytes()),
+		Some(H160(hex_literal::hex!("B5F762798A53D543A014CAF8B297CFF8F2F937E8")))
+	);
+	assert_eq!(decode_address(JUR_ISSUER.as_bytes()), Some(H160::from_low_u64_be(1)));
+	// Bad checksum
+	assert_eq!(decode_address(b"rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTi"), None);
+	assert_eq!(decode_address(b"0x1234"), None);
+}
+
+#[test]
+fn parse_value_works() {
+	assert_eq!(parse_value(b"100", 0), Some(100));
+	assert_eq!(parse_value(b"1.5", 6), Some(1_500_000));
+	assert_eq!(parse_value(b"2e3", 6), Some(2_000_000_000));
+	assert_eq!(parse_value(b"25E-1", 6), Some(2_500_000));
+	assert_eq!(parse_value(b"0.000001", 6), Some(1));
+	// Below the precision relayed
+	assert_eq!(parse_value(b"1e-7", 6), None);
+	assert_eq!(parse_value(b"1.0000005", 6), None);
+	assert_eq!(parse_value(b"-1", 6), None);
+	assert_eq!(parse_value(b"", 6), None);
+}
+
+#[test]
+fn watcher_accepts_matching_transactions() {
+	let (mut ext, offchain, pool) = watcher_ext();
+	expect_transaction(&offchain, 100, 555, "1000");
+
+	ext.execute_with(|| {
+		assert_ok!(Bridge::watch_transactions(1));
+		assert!(pool.read().transactions.is_empty());
+		// Verified transactions are not looked up again
+		assert_ok!(Bridge::watch_transactions(2));
+		assert!(pool.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn watcher_forgets_transactions_no_longer_pending() {
+	let (mut ext, offchain, _) = watcher_ext();
+	expect_transaction(&offchain, 100, 555, "1000");
+
+	ext.execute_with(|| {
+		let transaction_hash = TxHash::from_low_u64_be(1);
+		let watched = || StorageValueRef::persistent(WATCHED_KEY).get::<Vec<TxHash>>();
+		assert_ok!(Bridge::watch_transactions(1));
+		assert_eq!(watched(), Ok(Some(vec![transaction_hash])));
+
+		let process_block = match Bridge::transaction_status(transaction_hash) {
+			Some(TxStatus::Pending(ProcessAt::Block(block_number))) => block_number,
+			status => panic!("unexpected status {:?}", status),
+		};
+		Bridge::on_initialize(process_block);
+		assert!(!matches!(
+			Bridge::transaction_status(transaction_hash),
+			Some(TxStatus::Pending(_))
+		));
+		assert_ok!(Bridge::watch_transactions(process_block));
+		assert_eq!(watched(), Ok(Some(vec![])));
+	});
+}
+
+#[test]
+fn watcher_challenges_amount_mismatch() {
+	let (mut ext, offchain, pool) = watcher_ext();
+	expect_transaction(&offchain, 100, 555, "999");
+
+	ext.execute_with(|| {
+		assert_ok!(Bridge::watch_transactions(1));
+		assert_challenged(&pool, ChallengeReason::AmountMismatch, "ledger amount 999000000");
+	});
+}
+
+#[test]
+fn watcher_challenges_destination_mismatch() {
+	let (mut ext, offchain, pool) = watcher_ext();
+	expect_transaction(&offchain, 100, 556, "1000");
+
+	ext.execute_with(|| {
+		assert_ok!(Bridge::watch_transactions(1));
+		assert_challenged(
+			&pool,
+			ChallengeReason::DestinationMismatch,
+			&format!("ledger destination {:040X}", 556),
+		);
+	});
+}
+
+#[test]
+fn watcher_challenges_ledger_index_mismatch() {
+	let (mut ext, offchain, pool) = watcher_ext();
+	expect_transaction(&offchain, 101, 555, "1000");
+
+	ext.execute_with(|| {
+		assert_ok!(Bridge::watch_transactions(1));
+		assert_challenged(&pool, ChallengeReason::Other, "ledger index 101");
+	});
+}
+
+#[test]
+fn watcher_challenges_missing_transactions() {
+	let (mut ext, offchain, pool) = watcher_ext();
+	expect_transaction_lookup(
+		&offchain,
+		r#"{"result":{"error":"txnNotFound","searched_all":true,"status":"error"}}"#.into(),
+	);
+
+	ext.execute_with(|| {
+		assert_ok!(Bridge::watch_transactions(1));
+		assert_challenged(&pool, ChallengeReason::NotOnLedger, "transaction not found");
+	});
+}
+
+#[test]
+fn watcher_looks_up_again_transactions_not_searched_for() {
+	let (mut ext, offchain, pool) = watcher_ext();
+	// The node does not have the whole ledger range yet
+	expect_transaction_lookup(
+		&offchain,
+		r#"{"result":{"error":"txnNotFound","searched_all":false,"status":"error"}}"#.into(),
+	);
+
+	ext.execute_with(|| {
+		assert_ok!(Bridge::watch_transactions(1));
+		assert!(pool.read().transactions.is_empty());
+	});
+
+	expect_transaction(&offchain, 100, 555, "999");
+	ext.execute_with(|| {
+		assert_ok!(Bridge::watch_transactions(2));
+		assert_challenged(&pool, ChallengeReason::AmountMismatch, "ledger amount 999000000");
+	});
+}
+
+#[test]
+fn watcher_skips_transactions_past_challenge_period() {
+	let (mut ext, _, pool) = watcher_ext();
+	ext.execute_with(|| {
+		let process_block = match Bridge::transaction_status(TxHash::from_low_u64_be(1)) {
+			Some(TxStatus::Pending(ProcessAt::Block(block_number))) => block_number,
+			status => panic!("unexpected status {:?}", status),
+		};
+		// A challenge would only be included once the transaction is processed
+		assert_ok!(Bridge::watch_transactions(process_block - 1));
+		assert!(pool.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn watcher_does_nothing_when_challenges_paused() {
+	let (mut ext, _, pool) = watcher_ext();
+	ext.execute_with(|| {
+		assert_ok!(Bridge::set_bridge_mode(
+			RuntimeOrigin::root(),
+			BridgeMode { challenges_paused: true, ..Default::default() }
+		));
+		assert_ok!(Bridge::watch_transactions(1));
+		assert!(pool.read().transactions.is_empty());
+	});
+}
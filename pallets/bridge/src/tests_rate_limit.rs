@@ -0,0 +1,298 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::*;
+use sp_core::H160;
+
+fn process_block(n: u64) {
+	System::set_block_number(n);
+	Bridge::on_initialize(n);
+}
+
+#[test]
+fn set_rate_limits_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let limit = RateLimit { amount: token(1000), period: 20 };
+
+		assert_noop!(
+			Bridge::set_asset_rate_limit(RuntimeOrigin::signed(relayer()), 2, Some(limit)),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_noop!(
+			Bridge::set_asset_rate_limit(
+				RuntimeOrigin::root(),
+				2,
+				Some(RateLimit { amount: token(1000), period: 0 })
+			),
+			Error::<Test>::InvalidRateLimit
+		);
+		assert_noop!(
+			Bridge::set_destination_rate_limit(
+				RuntimeOrigin::root(),
+				Some(RateLimit { amount: token(1000), period: 0 })
+			),
+			Error::<Test>::InvalidRateLimit
+		);
+
+		assert_ok!(Bridge::set_asset_rate_limit(RuntimeOrigin::root(), 2, Some(limit)));
+		assert_eq!(Bridge::asset_rate_limit(2), Some(limit));
+		System::assert_last_event(RuntimeEvent::Bridge(Event::AssetRateLimitSet(2, Some(limit))));
+		assert_ok!(Bridge::set_destination_rate_limit(RuntimeOrigin::root(), Some(limit)));
+		assert_eq!(Bridge::destination_rate_limit(), Some(limit));
+		System::assert_last_event(RuntimeEvent::Bridge(Event::DestinationRateLimitSet(Some(
+			limit,
+		))));
+
+		assert_ok!(Bridge::set_asset_rate_limit(RuntimeOrigin::root(), 2, None));
+		assert_eq!(Bridge::asset_rate_limit(2), None);
+		assert_ok!(Bridge::set_destination_rate_limit(RuntimeOrigin::root(), None));
+		assert_eq!(Bridge::destination_rate_limit(), None);
+	})
+}
+
+#[test]
+fn asset_rate_limit_defers_mint() {
+	new_test_ext().execute_with(|| {
+		Bridge::initialize_relayer(&vec![relayer()]);
+		assert_ok!(Bridge::set_asset_rate_limit(
+			RuntimeOrigin::root(),
+			TokenAssetId::get(),
+			Some(RateLimit { amount: token(1500), period: 20 })
+		));
+		let first = TxHash::from_low_u64_be(1);
+		submit_payment(first, token(1000), H160::from_low_u64_be(1));
+		let second = TxHash::from_low_u64_be(2);
+		submit_payment(second, token(1000), H160::from_low_u64_be(2));
+
+		let process_at = TxChallengePeriod::get() as u64;
+		process_block(process_at);
+		assert_eq!(Bridge::transaction_status(first), Some(TxStatus::Settled));
+		assert_eq!(token_balance_of(H160::from_low_u64_be(2)), 0);
+		assert_eq!(Bridge::transaction_status(second), Some(TxStatus::Deferred(process_at + 20)));
+		assert_eq!(Bridge::process_transaction(process_at + 20).unwrap(), vec![second]);
+		System::assert_last_event(RuntimeEvent::Bridge(Event::MintDeferred(
+			2,
+			second,
+			RateLimitScope::Asset(TokenAssetId::get()),
+			process_at + 20,
+		)));
+		// Its challenge period is over
+		assert_noop!(
+			Bridge::submit_challenge(
+				RuntimeOrigin::signed(relayer()),
+				second,
+				ChallengeReason::AmountMismatch,
+				None
+			),
+			Error::<Test>::ChallengePeriodOver
+		);
+
+		// A new window starts once the first one has ended
+		process_block(process_at + 20);
+		assert_eq!(token_balance_of(H160::from_low_u64_be(2)), token(1000));
+		assert_eq!(
+			Bridge::asset_mint_window(TokenAssetId::get()),
+			Some(MintWindow { start: process_at + 20, minted: token(1000) })
+		);
+	})
+}
+
+#[test]
+fn destination_rate_limit_defers_mint() {
+	new_test_ext().execute_with(|| {
+		Bridge::initialize_relayer(&vec![relayer()]);
+		assert_ok!(Bridge::set_destination_rate_limit(
+			RuntimeOrigin::root(),
+			Some(RateLimit { amount: token(1000), period: 10 })
+		));
+		let destination = H160::from_low_u64_be(1);
+		let other = H160::from_low_u64_be(2);
+		submit_payment(TxHash::from_low_u64_be(1), token(600), destination);
+		let deferred = TxHash::from_low_u64_be(2);
+		submit_payment(deferred, token(600), destination);
+		submit_payment(TxHash::from_low_u64_be(3), token(600), other);
+
+		let process_at = TxChallengePeriod::get() as u64;
+		process_block(process_at);
+		assert_eq!(token_balance_of(destination), token(600));
+		assert_eq!(token_balance_of(other), token(600));
+		System::assert_has_event(RuntimeEvent::Bridge(Event::MintDeferred(
+			2,
+			deferred,
+			RateLimitScope::Destination(destination),
+			process_at + 10,
+		)));
+
+		process_block(process_at + 10);
+		assert_eq!(token_balance_of(destination), token(1200));
+	})
+}
+
+#[test]
+fn deferred_mint_overflows_to_next_block() {
+	new_test_ext().execute_with(|| {
+		MaxTransactionsPerBlock::set(1);
+		Bridge::initialize_relayer(&vec![relayer()]);
+		assert_ok!(Bridge::set_destination_rate_limit(
+			RuntimeOrigin::root(),
+			Some(RateLimit { amount: token(1000), period: 10 })
+		));
+		let destination = H160::from_low_u64_be(1);
+		submit_payment(TxHash::from_low_u64_be(1), token(600), destination);
+		System::set_block_number(1);
+		submit_payment(TxHash::from_low_u64_be(2), token(600), destination);
+		System::set_block_number(10);
+		submit_payment(TxHash::from_low_u64_be(3), token(100), H160::from_low_u64_be(2));
+
+		let process_at = TxChallengePeriod::get() as u64;
+		process_block(process_at);
+		process_block(process_at + 1);
+
+		// The end of the window is taken by the third transaction
+		assert_eq!(
+			Bridge::transaction_status(TxHash::from_low_u64_be(2)),
+			Some(TxStatus::Deferred(process_at + 11))
+		);
+	})
+}
+
+#[test]
+fn deferred_mint_fails_without_room_left() {
+	new_test_ext().execute_with(|| {
+		MaxTransactionsPerBlock::set(1);
+		Bridge::initialize_relayer(&vec![relayer()]);
+		assert_ok!(Bridge::set_destination_rate_limit(
+			RuntimeOrigin::root(),
+			Some(RateLimit { amount: token(1000), period: 10 })
+		));
+		let destination = H160::from_low_u64_be(1);
+		submit_payment(TxHash::from_low_u64_be(1), token(600), destination);
+		System::set_block_number(1);
+		let transaction_hash = TxHash::from_low_u64_be(2);
+		submit_payment(transaction_hash, token(600), destination);
+
+		let process_at = TxChallengePeriod::get() as u64;
+		for offset in 0..MAX_RESCHEDULE_BLOCKS as u64 {
+			assert_ok!(ProcessTransaction::<Test>::try_append(
+				process_at + 10 + offset,
+				TxHash::from_low_u64_be(100 + offset)
+			));
+		}
+		process_block(process_at);
+		process_block(process_at + 1);
+
+		assert_eq!(Bridge::transaction_status(transaction_hash), Some(TxStatus::Failed));
+		assert_eq!(
+			Bridge::failed_transactions(transaction_hash),
+			Some(Error::<Test>::TooManyTransactions.into())
+		);
+	})
+}
+
+#[test]
+fn retry_failed_respects_rate_limits() {
+	new_test_ext().execute_with(|| {
+		Bridge::initialize_relayer(&vec![relayer()]);
+		let destination = H160::from_low_u64_be(1);
+		let transaction_hash = TxHash::from_low_u64_be(1);
+		submit_payment(transaction_hash, token(1000), destination);
+		assert_ok!(Bridge::deregister_currency(RuntimeOrigin::root(), jur_currency()));
+		let process_at = TxChallengePeriod::get() as u64;
+		process_block(process_at);
+		assert_eq!(Bridge::transaction_status(transaction_hash), Some(TxStatus::Failed));
+
+		assert_ok!(Bridge::register_currency(
+			RuntimeOrigin::root(),
+			jur_currency(),
+			TokenAssetId::get()
+		));
+		assert_ok!(Bridge::set_destination_rate_limit(
+			RuntimeOrigin::root(),
+			Some(RateLimit { amount: token(500), period: 10 })
+		));
+		assert_noop!(
+			Bridge::retry_failed(RuntimeOrigin::signed(relayer()), transaction_hash),
+			Error::<Test>::RateLimitExceeded
+		);
+
+		assert_ok!(Bridge::set_destination_rate_limit(
+			RuntimeOrigin::root(),
+			Some(RateLimit { amount: token(1000), period: 10 })
+		));
+		assert_ok!(Bridge::retry_failed(RuntimeOrigin::signed(relayer()), transaction_hash));
+		assert_eq!(token_balance_of(destination), token(1000));
+		assert_eq!(
+			Bridge::destination_mint_window(destination),
+			Some(MintWindow { start: process_at, minted: token(1000) })
+		);
+	})
+}
+
+#[test]
+fn payment_over_asset_rate_limit_fails() {
+	new_test_ext().execute_with(|| {
+		Bridge::initialize_relayer(&vec![relayer()]);
+		assert_ok!(Bridge::set_asset_rate_limit(
+			RuntimeOrigin::root(),
+			TokenAssetId::get(),
+			Some(RateLimit { amount: token(500), period: 20 })
+		));
+		let destination = H160::from_low_u64_be(1);
+		let transaction_hash = TxHash::from_low_u64_be(1);
+		submit_payment(transaction_hash, token(1000), destination);
+
+		let process_at = TxChallengePeriod::get() as u64;
+		process_block(process_at);
+		assert_eq!(Bridge::transaction_status(transaction_hash), Some(TxStatus::Failed));
+		assert_eq!(
+			Bridge::failed_transactions(transaction_hash),
+			Some(Error::<Test>::RateLimitExceeded.into())
+		);
+		assert_eq!(Bridge::process_transaction(process_at + 20), None);
+		System::assert_last_event(RuntimeEvent::Bridge(Event::MintExceedsRateLimit(
+			1,
+			transaction_hash,
+			RateLimitScope::Asset(TokenAssetId::get()),
+		)));
+		assert_noop!(
+			Bridge::retry_failed(RuntimeOrigin::signed(relayer()), transaction_hash),
+			Error::<Test>::RateLimitExceeded
+		);
+
+		assert_ok!(Bridge::set_asset_rate_limit(
+			RuntimeOrigin::root(),
+			TokenAssetId::get(),
+			Some(RateLimit { amount: token(1000), period: 20 })
+		));
+		assert_ok!(Bridge::retry_failed(RuntimeOrigin::signed(relayer()), transaction_hash));
+		assert_eq!(token_balance_of(destination), token(1000));
+	})
+}
+
+#[test]
+fn payment_over_destination_rate_limit_fails() {
+	new_test_ext().execute_with(|| {
+		Bridge::initialize_relayer(&vec![relayer()]);
+		assert_ok!(Bridge::set_destination_rate_limit(
+			RuntimeOrigin::root(),
+			Some(RateLimit { amount: token(500), period: 10 })
+		));
+		let destination = H160::from_low_u64_be(1);
+		let transaction_hash = TxHash::from_low_u64_be(1);
+		submit_payment(transaction_hash, token(1000), destination);
+
+		let process_at = TxChallengePeriod::get() as u64;
+		process_block(process_at);
+		assert_eq!(Bridge::transaction_status(transaction_hash), Some(TxStatus::Failed));
+		assert_eq!(Bridge::process_transaction(process_at + 10), None);
+		System::assert_last_event(RuntimeEvent::Bridge(Event::MintExceedsRateLimit(
+			1,
+			transaction_hash,
+			RateLimitScope::Destination(destination),
+		)));
+
+		assert_ok!(Bridge::set_destination_rate_limit(RuntimeOrigin::root(), None));
+		assert_ok!(Bridge::retry_failed(RuntimeOrigin::signed(relayer()), transaction_hash));
+		assert_eq!(token_balance_of(destination), token(1000));
+	})
+}
@@ -1,5 +1,5 @@
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::GenesisBuild};
 use mock::*;
 use sp_core::H160;
 use sp_runtime::traits::BadOrigin;
@@ -71,3 +71,187 @@ fn test_is_relayer_works() {
 		assert_eq!(Bridge::get_relayer(relayer2), None);
 	})
 }
+
+#[test]
+fn test_add_relayer_bonds_min_stake() {
+	new_test_ext().execute_with(|| {
+		let relayer = AccountId::from(H160::from_slice(b"6490B68F1116BFE87DDD"));
+		let unfunded = AccountId::from(H160::from_slice(b"6490B68F1116BFE87DDF"));
+
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer));
+		assert_eq!(Balances::reserved_balance(relayer), RelayerMinStake::get());
+		assert_eq!(
+			Bridge::relayer_bond(relayer).map(|bond| bond.active),
+			Some(RelayerMinStake::get())
+		);
+
+		// Adding again does not bond twice
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer));
+		assert_eq!(Balances::reserved_balance(relayer), RelayerMinStake::get());
+
+		assert!(Bridge::add_relayer(RuntimeOrigin::root(), unfunded).is_err());
+		assert_eq!(Bridge::get_relayer(unfunded), None);
+	})
+}
+
+#[test]
+fn test_register_relayer_works() {
+	new_test_ext().execute_with(|| {
+		let relayer = AccountId::from(H160::from_slice(b"6490B68F1116BFE87DDD"));
+		assert_ok!(Bridge::set_relayer_threshold(RuntimeOrigin::root(), 2));
+
+		assert_noop!(
+			Bridge::register_relayer(RuntimeOrigin::signed(relayer), RelayerMinStake::get() - 1),
+			Error::<Test>::InsufficientStake
+		);
+		assert_ok!(Bridge::register_relayer(
+			RuntimeOrigin::signed(relayer),
+			RelayerMinStake::get()
+		));
+		assert_eq!(Bridge::get_relayer(relayer), Some(true));
+		assert_eq!(Balances::reserved_balance(relayer), RelayerMinStake::get());
+
+		// Top up an existing stake
+		assert_ok!(Bridge::register_relayer(RuntimeOrigin::signed(relayer), 1));
+		assert_eq!(Balances::reserved_balance(relayer), RelayerMinStake::get() + 1);
+	})
+}
+
+#[test]
+fn test_removed_relayer_cannot_register_again() {
+	new_test_ext().execute_with(|| {
+		let relayer = AccountId::from(H160::from_slice(b"6490B68F1116BFE87DDD"));
+		assert_ok!(Bridge::set_relayer_threshold(RuntimeOrigin::root(), 2));
+		assert_ok!(Bridge::register_relayer(
+			RuntimeOrigin::signed(relayer),
+			RelayerMinStake::get()
+		));
+		assert_ok!(Bridge::remove_relayer(RuntimeOrigin::root(), relayer));
+
+		assert_noop!(
+			Bridge::register_relayer(RuntimeOrigin::signed(relayer), RelayerMinStake::get()),
+			Error::<Test>::RelayerBanned
+		);
+		assert_eq!(Bridge::get_relayer(relayer), None);
+
+		// Governance can still add it back, after which it can top up its stake again
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer));
+		assert_eq!(Bridge::get_relayer(relayer), Some(true));
+		assert_ok!(Bridge::register_relayer(RuntimeOrigin::signed(relayer), 1));
+	})
+}
+
+#[test]
+fn test_register_relayer_requires_threshold_above_one() {
+	new_test_ext().execute_with(|| {
+		let relayer = AccountId::from(H160::from_slice(b"6490B68F1116BFE87DDD"));
+		assert_noop!(
+			Bridge::register_relayer(RuntimeOrigin::signed(relayer), RelayerMinStake::get()),
+			Error::<Test>::RegistrationClosed
+		);
+
+		// Relayers added by governance can still top up their stake
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer));
+		assert_ok!(Bridge::register_relayer(RuntimeOrigin::signed(relayer), 1));
+	})
+}
+
+#[test]
+fn test_relayers_are_bounded_by_max_relayers() {
+	new_test_ext().execute_with(|| {
+		MaxRelayers::set(2);
+		assert_ok!(Bridge::set_relayer_threshold(RuntimeOrigin::root(), 2));
+		let relayer = AccountId::from(H160::from_slice(b"6490B68F1116BFE87DDD"));
+		let relayer2 = AccountId::from(H160::from_slice(b"6490B68F1116BFE87DDE"));
+		let relayer3 = AccountId::from(H160::from_slice(b"6490B68F1116BFE87DDC"));
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer));
+		assert_ok!(Bridge::register_relayer(
+			RuntimeOrigin::signed(relayer2),
+			RelayerMinStake::get()
+		));
+		assert_eq!(Bridge::relayer_count(), 2);
+
+		assert_noop!(
+			Bridge::register_relayer(RuntimeOrigin::signed(relayer3), RelayerMinStake::get()),
+			Error::<Test>::TooManyRelayers
+		);
+		assert_noop!(
+			Bridge::add_relayer(RuntimeOrigin::root(), relayer3),
+			Error::<Test>::TooManyRelayers
+		);
+		// Known relayers can still top up their stake
+		assert_ok!(Bridge::register_relayer(RuntimeOrigin::signed(relayer2), 1));
+
+		// Removing a relayer frees its slot
+		assert_ok!(Bridge::remove_relayer(RuntimeOrigin::root(), relayer));
+		assert_eq!(Bridge::relayer_count(), 1);
+		assert_ok!(Bridge::add_relayer(RuntimeOrigin::root(), relayer3));
+	})
+}
+
+#[test]
+fn test_unbond_relayer_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let relayer = AccountId::from(H160::from_slice(b"6490B68F1116BFE87DDD"));
+		assert_ok!(Bridge::set_relayer_threshold(RuntimeOrigin::root(), 2));
+		assert_ok!(Bridge::register_relayer(
+			RuntimeOrigin::signed(relayer),
+			RelayerMinStake::get()
+		));
+
+		assert_noop!(
+			Bridge::unbond_relayer(RuntimeOrigin::signed(relayer), RelayerMinStake::get() + 1),
+			Error::<Test>::InsufficientStake
+		);
+		assert_ok!(Bridge::unbond_relayer(RuntimeOrigin::signed(relayer), 1));
+		// Falling below the minimum stake deactivates the relayer
+		assert_eq!(Bridge::get_relayer(relayer), Some(false));
+		System::assert_last_event(RuntimeEvent::Bridge(Event::RelayerDeactivated(relayer)));
+
+		// Unbonding stake stays locked until the unbonding period is over
+		assert_noop!(
+			Bridge::withdraw_unbonded(RuntimeOrigin::signed(relayer)),
+			Error::<Test>::NoUnbondedStake
+		);
+		System::set_block_number(1 + RelayerUnbondingPeriod::get() as u64);
+		assert_ok!(Bridge::withdraw_unbonded(RuntimeOrigin::signed(relayer)));
+		assert_eq!(Balances::reserved_balance(relayer), RelayerMinStake::get() - 1);
+		assert_noop!(
+			Bridge::withdraw_unbonded(RuntimeOrigin::signed(relayer)),
+			Error::<Test>::NoUnbondedStake
+		);
+	})
+}
+
+fn genesis_ext(relayers: Vec<AccountId>) -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(create_account(b"6490B68F1116BFE87DDD"), INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	crate::GenesisConfig::<Test> { relayers, relayer_threshold: 1, currencies: vec![] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	t.into()
+}
+
+#[test]
+fn test_genesis_relayers_bond_min_stake() {
+	let relayer = create_account(b"6490B68F1116BFE87DDD");
+	genesis_ext(vec![relayer]).execute_with(|| {
+		assert_eq!(Bridge::get_relayer(relayer), Some(true));
+		assert_eq!(Balances::reserved_balance(relayer), RelayerMinStake::get());
+		assert_eq!(
+			Bridge::relayer_bond(relayer).map(|bond| bond.active),
+			Some(RelayerMinStake::get())
+		);
+	})
+}
+
+#[test]
+#[should_panic(expected = "Genesis relayers must be able to bond RelayerMinStake")]
+fn test_genesis_relayers_without_stake_fail() {
+	genesis_ext(vec![create_account(b"6490B68F1116BFE87DDF")]);
+}
@@ -0,0 +1,99 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::*;
+use sp_core::H160;
+
+fn fund_account(account: AccountId, amount: Balance) {
+	assert_ok!(<Test as Config>::Assets::mint_into(TokenAssetId::get(), &account, amount));
+}
+
+#[test]
+fn withdraw_burns_and_records_request() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let who = create_account(b"6490B68F1116BFE87DDC");
+		let destination = H160::from_low_u64_be(555);
+		fund_account(who, token(1000));
+
+		assert_ok!(Bridge::withdraw(RuntimeOrigin::signed(who), token(400), destination));
+		assert_ok!(Bridge::withdraw(RuntimeOrigin::signed(who), token(100), destination));
+
+		assert_eq!(token_balance_of(who), token(500));
+		assert_eq!(Bridge::next_withdrawal_id(), 2);
+		assert_eq!(
+			Bridge::withdrawal_requests(0),
+			Some(WithdrawalRequest {
+				who,
+				amount: token(400),
+				destination,
+				status: WithdrawalStatus::Requested,
+			})
+		);
+		System::assert_last_event(RuntimeEvent::Bridge(Event::WithdrawalRequested(
+			1,
+			who,
+			token(100),
+			destination,
+		)));
+	})
+}
+
+#[test]
+fn withdraw_fails_without_funds() {
+	new_test_ext().execute_with(|| {
+		let who = create_account(b"6490B68F1116BFE87DDC");
+		let destination = H160::from_low_u64_be(555);
+
+		assert_noop!(
+			Bridge::withdraw(RuntimeOrigin::signed(who), 0, destination),
+			Error::<Test>::InvalidAmount
+		);
+		assert!(Bridge::withdraw(RuntimeOrigin::signed(who), token(1), destination).is_err());
+		assert_eq!(Bridge::next_withdrawal_id(), 0);
+	})
+}
+
+#[test]
+fn execute_withdrawal_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let who = create_account(b"6490B68F1116BFE87DDC");
+		let relayer = create_account(b"6490B68F1116BFE87DDD");
+		let destination = H160::from_low_u64_be(555);
+		let transaction_hash = TxHash::from_low_u64_be(123);
+		Bridge::initialize_relayer(&vec![relayer]);
+		fund_account(who, token(1000));
+		assert_ok!(Bridge::withdraw(RuntimeOrigin::signed(who), token(1000), destination));
+
+		// Only relayers can confirm the payout
+		assert_noop!(
+			Bridge::execute_withdrawal(RuntimeOrigin::signed(who), 0, 7, transaction_hash),
+			Error::<Test>::NotPermitted
+		);
+		assert_noop!(
+			Bridge::execute_withdrawal(RuntimeOrigin::signed(relayer), 1, 7, transaction_hash),
+			Error::<Test>::UnknownWithdrawal
+		);
+
+		assert_ok!(Bridge::execute_withdrawal(
+			RuntimeOrigin::signed(relayer),
+			0,
+			7,
+			transaction_hash
+		));
+		assert_eq!(
+			Bridge::withdrawal_requests(0).map(|request| request.status),
+			Some(WithdrawalStatus::Executed { ledger_index: 7, transaction_hash })
+		);
+		System::assert_last_event(RuntimeEvent::Bridge(Event::WithdrawalExecuted(
+			0,
+			7,
+			transaction_hash,
+		)));
+
+		assert_noop!(
+			Bridge::execute_withdrawal(RuntimeOrigin::signed(relayer), 0, 7, transaction_hash),
+			Error::<Test>::WithdrawalAlreadyExecuted
+		);
+	})
+}
@@ -0,0 +1,452 @@
+//! Weights for pallet_bridge
+//!
+//! PLACEHOLDER VALUES, NOT BENCHMARK OUTPUT. The execution times below are hand estimates and
+//! only the storage accesses are counted from the code, the attestations cleared per transaction
+//! are charged as the product of both components. Do not ship a production runtime with them,
+//! regenerate this file with `make bridge-weights` on reference hardware first.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_bridge.
+pub trait WeightInfo {
+	fn submit_transaction(r: u32, ) -> Weight;
+	fn submit_challenge() -> Weight;
+	fn failed_challenge() -> Weight;
+	fn success_challenge(r: u32, ) -> Weight;
+	fn add_relayer() -> Weight;
+	fn remove_relayer() -> Weight;
+	fn register_relayer() -> Weight;
+	fn unbond_relayer() -> Weight;
+	fn withdraw_unbonded() -> Weight;
+	fn set_relayer_threshold() -> Weight;
+	fn set_challenge_period_tiers(n: u32, ) -> Weight;
+	fn set_asset_rate_limit() -> Weight;
+	fn set_destination_rate_limit() -> Weight;
+	fn set_bridge_mode() -> Weight;
+	fn set_fee_schedule() -> Weight;
+	fn set_minimum_fee() -> Weight;
+	fn register_currency() -> Weight;
+	fn deregister_currency() -> Weight;
+	fn retry_failed() -> Weight;
+	fn admit_transaction() -> Weight;
+	fn withdraw() -> Weight;
+	fn execute_withdrawal() -> Weight;
+	fn report_reserve(r: u32, ) -> Weight;
+	fn process_tx(n: u32, r: u32, ) -> Weight;
+	fn clear_storages(n: u32, r: u32, ) -> Weight;
+	fn expire_challenges(n: u32, r: u32, ) -> Weight;
+}
+
+/// Weights for pallet_bridge using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Bridge Relayer (r:1 w:0)
+	// Storage: Bridge TransactionStatus (r:1 w:1)
+	// Storage: Bridge TransactionAttestations (r:1 w:1)
+	// Storage: Bridge CurrencyRegistry (r:1 w:0)
+	// Storage: Bridge RelayerThreshold (r:1 w:0)
+	// Storage: Bridge ProcessTransactionDetails (r:0 w:1)
+	// Storage: Bridge ProcessTransaction (r:1 w:1)
+	// Storage: Bridge AdmittedTransactions (r:1 w:1)
+	// Storage: Bridge TransactionAttestations (r:1 w:1) per attestation
+	// Storage: Bridge Relayer (r:1 w:0) per attestation
+	fn submit_transaction(r: u32, ) -> Weight {
+		Weight::from_ref_time(45_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(4_000_000 as u64).saturating_mul(r as u64))
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(r as u64)))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(r as u64)))
+	}
+	// Storage: Bridge TransactionStatus (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Bridge ChallengeTransactionList (r:0 w:1)
+	// Storage: Bridge ChallengeExpiries (r:1 w:1)
+	fn submit_challenge() -> Weight {
+		Weight::from_ref_time(40_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Bridge TransactionStatus (r:1 w:1)
+	// Storage: Bridge ChallengeTransactionList (r:1 w:1)
+	// Storage: Bridge ProcessTransactionDetails (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Bridge ProcessTransaction (r:1 w:1)
+	fn failed_challenge() -> Weight {
+		Weight::from_ref_time(52_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: Bridge TransactionStatus (r:1 w:1)
+	// Storage: Bridge ChallengeTransactionList (r:1 w:1)
+	// Storage: Bridge ProcessTransactionDetails (r:1 w:1)
+	// Storage: Bridge TransactionAttestations (r:1 w:1)
+	// Storage: Bridge RelayerBonds (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Bridge Relayer (r:0 w:1)
+	// Storage: Bridge ProcessTransaction (r:1 w:1)
+	fn success_challenge(r: u32, ) -> Weight {
+		Weight::from_ref_time(60_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(30_000_000 as u64).saturating_mul(r as u64))
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().reads((3 as u64).saturating_mul(r as u64)))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+			.saturating_add(T::DbWeight::get().writes((4 as u64).saturating_mul(r as u64)))
+	}
+	// Storage: Bridge RelayerBonds (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Bridge Relayer (r:0 w:1)
+	// Storage: Bridge RemovedRelayers (r:0 w:1)
+	// Storage: Bridge RelayerCount (r:1 w:1)
+	fn add_relayer() -> Weight {
+		Weight::from_ref_time(30_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: Bridge Relayer (r:1 w:1)
+	// Storage: Bridge RelayerBonds (r:1 w:1)
+	// Storage: Bridge RemovedRelayers (r:0 w:1)
+	// Storage: Bridge RelayerCount (r:1 w:1)
+	fn remove_relayer() -> Weight {
+		Weight::from_ref_time(25_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Bridge RemovedRelayers (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Bridge RelayerBonds (r:1 w:1)
+	// Storage: Bridge Relayer (r:1 w:1)
+	// Storage: Bridge RelayerThreshold (r:1 w:0)
+	// Storage: Bridge RelayerCount (r:1 w:1)
+	fn register_relayer() -> Weight {
+		Weight::from_ref_time(32_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Bridge RelayerBonds (r:1 w:1)
+	// Storage: Bridge Relayer (r:0 w:1)
+	fn unbond_relayer() -> Weight {
+		Weight::from_ref_time(24_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Bridge RelayerBonds (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_ref_time(28_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Bridge RelayerThreshold (r:0 w:1)
+	fn set_relayer_threshold() -> Weight {
+		Weight::from_ref_time(15_000_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Bridge ChallengePeriodTiers (r:0 w:1)
+	fn set_challenge_period_tiers(n: u32, ) -> Weight {
+		Weight::from_ref_time(16_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(150_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Bridge AssetRateLimits (r:0 w:1)
+	// Storage: Bridge AssetMintWindows (r:0 w:1)
+	fn set_asset_rate_limit() -> Weight {
+		Weight::from_ref_time(17_000_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Bridge DestinationRateLimit (r:0 w:1)
+	fn set_destination_rate_limit() -> Weight {
+		Weight::from_ref_time(15_000_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Bridge CurrentBridgeMode (r:0 w:1)
+	fn set_bridge_mode() -> Weight {
+		Weight::from_ref_time(14_000_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Bridge BridgeFeeSchedule (r:0 w:1)
+	fn set_fee_schedule() -> Weight {
+		Weight::from_ref_time(14_000_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Bridge MinimumFees (r:0 w:1)
+	fn set_minimum_fee() -> Weight {
+		Weight::from_ref_time(15_000_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Bridge CurrencyRegistry (r:0 w:1)
+	fn register_currency() -> Weight {
+		Weight::from_ref_time(16_000_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Bridge CurrencyRegistry (r:1 w:1)
+	fn deregister_currency() -> Weight {
+		Weight::from_ref_time(18_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Bridge FailedTransactions (r:1 w:1)
+	// Storage: Bridge ProcessTransactionDetails (r:1 w:0)
+	// Storage: Bridge CurrencyRegistry (r:1 w:0)
+	// Storage: Bridge AssetRateLimits (r:1 w:0)
+	// Storage: Bridge AssetMintWindows (r:1 w:1)
+	// Storage: Bridge DestinationRateLimit (r:1 w:0)
+	// Storage: Bridge DestinationMintWindows (r:1 w:1)
+	// Storage: Bridge BridgeFeeSchedule (r:1 w:0)
+	// Storage: Bridge MinimumFees (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:3 w:3)
+	// Storage: Bridge RelayerEarnings (r:1 w:1)
+	// Storage: Bridge SettledTransactionDetails (r:1 w:1)
+	// Storage: Bridge TransactionStatus (r:0 w:1)
+	fn retry_failed() -> Weight {
+		Weight::from_ref_time(82_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(15 as u64))
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
+	}
+	// Storage: Bridge TransactionStatus (r:1 w:0)
+	// Storage: Bridge AdmittedTransactions (r:0 w:1)
+	fn admit_transaction() -> Weight {
+		Weight::from_ref_time(17_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:1 w:1)
+	// Storage: Bridge NextWithdrawalId (r:1 w:1)
+	// Storage: Bridge WithdrawalRequests (r:0 w:1)
+	fn withdraw() -> Weight {
+		Weight::from_ref_time(42_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Bridge Relayer (r:1 w:0)
+	// Storage: Bridge WithdrawalRequests (r:1 w:1)
+	fn execute_withdrawal() -> Weight {
+		Weight::from_ref_time(22_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Bridge Relayer (r:1 w:0)
+	// Storage: Bridge RelayerBonds (r:1 w:0)
+	// Storage: Bridge CurrencyRegistry (r:1 w:0)
+	// Storage: Bridge ReserveReports (r:1 w:1)
+	// Storage: Bridge ReserveAttestations (r:1 w:1)
+	// Storage: Bridge RelayerThreshold (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: Bridge CurrentBridgeMode (r:1 w:1)
+	fn report_reserve(r: u32, ) -> Weight {
+		Weight::from_ref_time(36_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(5_000_000 as u64).saturating_mul(r as u64))
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(r as u64)))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(r as u64)))
+	}
+	// Storage: Bridge ProcessTransaction (r:1 w:1)
+	// Storage: Bridge TransactionStatus (r:1 w:1)
+	// Storage: Bridge ProcessTransactionDetails (r:1 w:0)
+	// Storage: Bridge CurrencyRegistry (r:1 w:0)
+	// Storage: Bridge AssetRateLimits (r:1 w:0)
+	// Storage: Bridge AssetMintWindows (r:1 w:1)
+	// Storage: Bridge DestinationRateLimit (r:1 w:0)
+	// Storage: Bridge DestinationMintWindows (r:1 w:1)
+	// Storage: Bridge BridgeFeeSchedule (r:1 w:0)
+	// Storage: Bridge MinimumFees (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:3 w:3)
+	// Storage: Bridge RelayerEarnings (r:1 w:1)
+	// Storage: Bridge SettledTransactionDetails (r:1 w:1)
+	fn process_tx(n: u32, _r: u32, ) -> Weight {
+		Weight::from_ref_time(5_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(70_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((15 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((9 as u64).saturating_mul(n as u64)))
+	}
+	// Storage: Bridge SettledTransactionDetails (r:1 w:1)
+	// Storage: Bridge ProcessTransactionDetails (r:0 w:1)
+	// Storage: Bridge TransactionAttestations (r:0 w:1) per attestation
+	fn clear_storages(n: u32, r: u32, ) -> Weight {
+		Weight::from_ref_time(4_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(9_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(Weight::from_ref_time(3_000_000 as u64).saturating_mul((n as u64).saturating_mul(r as u64)))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((n as u64).saturating_mul(r as u64)))
+	}
+	// Storage: Bridge ChallengeExpiries (r:1 w:1)
+	// Storage: Bridge ChallengeTransactionList (r:1 w:1)
+	// Storage: Bridge TransactionStatus (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Bridge ProcessTransaction (r:1 w:1)
+	// Storage: Bridge ProcessTransactionDetails (r:1 w:1)
+	// Storage: Bridge TransactionAttestations (r:0 w:1) per attestation
+	fn expire_challenges(n: u32, r: u32, ) -> Weight {
+		Weight::from_ref_time(4_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(35_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(Weight::from_ref_time(3_000_000 as u64).saturating_mul((n as u64).saturating_mul(r as u64)))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((6 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((5 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((n as u64).saturating_mul(r as u64)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn submit_transaction(r: u32, ) -> Weight {
+		Weight::from_ref_time(45_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(4_000_000 as u64).saturating_mul(r as u64))
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(r as u64)))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(r as u64)))
+	}
+	fn submit_challenge() -> Weight {
+		Weight::from_ref_time(40_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn failed_challenge() -> Weight {
+		Weight::from_ref_time(52_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
+	fn success_challenge(r: u32, ) -> Weight {
+		Weight::from_ref_time(60_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(30_000_000 as u64).saturating_mul(r as u64))
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(r as u64)))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(r as u64)))
+	}
+	fn add_relayer() -> Weight {
+		Weight::from_ref_time(30_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
+	fn remove_relayer() -> Weight {
+		Weight::from_ref_time(25_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn register_relayer() -> Weight {
+		Weight::from_ref_time(32_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn unbond_relayer() -> Weight {
+		Weight::from_ref_time(24_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_ref_time(28_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn set_relayer_threshold() -> Weight {
+		Weight::from_ref_time(15_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_challenge_period_tiers(n: u32, ) -> Weight {
+		Weight::from_ref_time(16_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(150_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_asset_rate_limit() -> Weight {
+		Weight::from_ref_time(17_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn set_destination_rate_limit() -> Weight {
+		Weight::from_ref_time(15_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_bridge_mode() -> Weight {
+		Weight::from_ref_time(14_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_fee_schedule() -> Weight {
+		Weight::from_ref_time(14_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_minimum_fee() -> Weight {
+		Weight::from_ref_time(15_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn register_currency() -> Weight {
+		Weight::from_ref_time(16_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn deregister_currency() -> Weight {
+		Weight::from_ref_time(18_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn retry_failed() -> Weight {
+		Weight::from_ref_time(82_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(15 as u64))
+			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+	}
+	fn admit_transaction() -> Weight {
+		Weight::from_ref_time(17_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn withdraw() -> Weight {
+		Weight::from_ref_time(42_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn execute_withdrawal() -> Weight {
+		Weight::from_ref_time(22_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn report_reserve(r: u32, ) -> Weight {
+		Weight::from_ref_time(36_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(5_000_000 as u64).saturating_mul(r as u64))
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(r as u64)))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(r as u64)))
+	}
+	fn process_tx(n: u32, _r: u32, ) -> Weight {
+		Weight::from_ref_time(5_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(70_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((15 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((9 as u64).saturating_mul(n as u64)))
+	}
+	fn clear_storages(n: u32, r: u32, ) -> Weight {
+		Weight::from_ref_time(4_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(9_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(Weight::from_ref_time(3_000_000 as u64).saturating_mul((n as u64).saturating_mul(r as u64)))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((n as u64).saturating_mul(r as u64)))
+	}
+	fn expire_challenges(n: u32, r: u32, ) -> Weight {
+		Weight::from_ref_time(4_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(35_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(Weight::from_ref_time(3_000_000 as u64).saturating_mul((n as u64).saturating_mul(r as u64)))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((6 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((5 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((n as u64).saturating_mul(r as u64)))
+	}
+}
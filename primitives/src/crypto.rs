@@ -0,0 +1,76 @@
+//! Bridge relayer keys, kept in the keystore under `KEY_TYPE` next to the session keys
+//!
+//! Relayer keys are inserted with `author_insertKey`, or with the `key insert` command:
+//! `key insert --key-type brdg --scheme ecdsa`. The `BridgeSigner` address of a key is the
+//! relayer account.
+
+use crate::signature::{signing_digest, BridgeSignature, BridgeSigner};
+use sp_application_crypto::{app_crypto, ecdsa, KeyTypeId};
+#[cfg(feature = "std")]
+use sp_keystore::SyncCryptoStore;
+
+/// Key type of the bridge relayer keys
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"brdg");
+
+app_crypto!(ecdsa, KEY_TYPE);
+
+impl From<Public> for BridgeSigner {
+	fn from(public: Public) -> Self {
+		sp_core::ecdsa::Public::from(public).into()
+	}
+}
+
+/// Addresses of the relayer keys in `keystore`
+#[cfg(feature = "std")]
+pub fn signers(keystore: &dyn SyncCryptoStore) -> Vec<BridgeSigner> {
+	SyncCryptoStore::ecdsa_public_keys(keystore, KEY_TYPE)
+		.into_iter()
+		.map(BridgeSigner::from)
+		.collect()
+}
+
+/// Sign the `signing_digest` of `payload` with the relayer key of `signer` in `keystore`
+#[cfg(feature = "std")]
+pub fn sign(
+	keystore: &dyn SyncCryptoStore,
+	signer: &BridgeSigner,
+	payload: &[u8],
+) -> Option<BridgeSignature> {
+	let key = SyncCryptoStore::ecdsa_public_keys(keystore, KEY_TYPE)
+		.into_iter()
+		.find(|key| BridgeSigner::from(*key) == *signer)?;
+	SyncCryptoStore::ecdsa_sign_prehashed(keystore, KEY_TYPE, &key, &signing_digest(payload))
+		.ok()
+		.flatten()
+		.map(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_keystore::testing::KeyStore;
+	use sp_runtime::traits::{IdentifyAccount, Verify};
+
+	#[test]
+	fn keystore_keys_sign_bridge_signatures() {
+		let keystore = KeyStore::new();
+		let public =
+			SyncCryptoStore::ecdsa_generate_new(&keystore, KEY_TYPE, Some("//Alice")).unwrap();
+		let signer = BridgeSigner::from(public);
+		assert_eq!(signers(&keystore), vec![signer.clone()]);
+		assert_eq!(BridgeSigner::from(Public::from(public)), signer);
+
+		let signature = sign(&keystore, &signer, b"payload").unwrap();
+		assert!(signature.verify(&b"payload"[..], &signer.clone().into_account()));
+		assert!(!signature.verify(&b"other payload"[..], &signer.into_account()));
+		assert!(sign(&keystore, &BridgeSigner::from([0u8; 20]), b"payload").is_none());
+	}
+
+	#[test]
+	fn other_key_types_are_ignored() {
+		let keystore = KeyStore::new();
+		SyncCryptoStore::ecdsa_generate_new(&keystore, KeyTypeId(*b"aura"), Some("//Alice"))
+			.unwrap();
+		assert!(signers(&keystore).is_empty());
+	}
+}
@@ -1,5 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod crypto;
 pub mod signature;
 
 /// Edit this file to define custom logic or remove it if it is not needed.
@@ -33,9 +34,15 @@ pub mod bridge {
 	/// An Bridge address (classic)
 	pub type Address = sp_core::H160;
 
+	/// A currency code on the external ledger, standard three letter codes use the 160-bit format
+	pub type CurrencyCode = [u8; 20];
+
 	/// An Bridge tx hash
 	pub type TxHash = sp_core::H512;
 
 	/// The type for identifying the Tx Nonce aka 'Sequence'
 	pub type TxNonce = u32;
+
+	/// Sequential identifier of an outbound withdrawal request
+	pub type WithdrawalId = u64;
 }
@@ -70,6 +70,12 @@ impl From<ecdsa::Signature> for BridgeSignature {
 	}
 }
 
+impl From<BridgeSignature> for ecdsa::Signature {
+	fn from(x: BridgeSignature) -> Self {
+		x.0
+	}
+}
+
 impl sp_runtime::traits::Verify for BridgeSignature {
 	type Signer = BridgeSigner;
 
@@ -79,7 +85,7 @@ impl sp_runtime::traits::Verify for BridgeSignature {
 	/// `keccak256(prefix + message.len() + message)`
 	fn verify<L: sp_runtime::traits::Lazy<[u8]>>(&self, mut msg: L, signer: &AccountId20) -> bool {
 		let message = msg.get();
-		let m = keccak_256(message);
+		let m = signing_digest(message);
 
 		let native_signature_valid =
 			match sp_io::crypto::secp256k1_ecdsa_recover(self.0.as_ref(), &m) {
@@ -120,6 +126,14 @@ impl sp_runtime::traits::Verify for BridgeSignature {
 	}
 }
 
+/// Digest a `BridgeSignature` of `payload` signs
+///
+/// `BridgeSignature` recovers the signer from the keccak digest of the message, while ecdsa
+/// keys hash with blake2 unless they sign a digest prehashed, so signers sign this one
+pub fn signing_digest(payload: &[u8]) -> [u8; 32] {
+	keccak_256(payload)
+}
+
 /// Constructs the message that Ethereum RPC's `personal_sign` and `eth_sign` would sign.
 pub fn personal_sign_message(message: &[u8]) -> Vec<u8> {
 	let mut l = message.len();
@@ -169,6 +183,15 @@ impl From<ecdsa::Public> for BridgeSigner {
 	}
 }
 
+/// The public key cannot be recovered from the address, required by `AppCrypto` only to verify
+/// signatures which `BridgeSignature` does from the address
+impl TryFrom<BridgeSigner> for ecdsa::Public {
+	type Error = ();
+	fn try_from(_: BridgeSigner) -> Result<Self, Self::Error> {
+		Err(())
+	}
+}
+
 impl From<libsecp256k1::PublicKey> for BridgeSigner {
 	fn from(x: libsecp256k1::PublicKey) -> Self {
 		let mut m = [0u8; 64];
@@ -0,0 +1,38 @@
+//! Last relayed ledger, kept in a file so that a restarted relayer resumes after it
+
+use crate::Error;
+use primitives::bridge::LedgerIndex;
+use std::{fs, io, path::PathBuf};
+
+pub struct Checkpoint {
+	path: PathBuf,
+}
+
+impl Checkpoint {
+	pub fn new(path: impl Into<PathBuf>) -> Self {
+		Self { path: path.into() }
+	}
+
+	/// Last relayed ledger, `None` before the first one
+	pub fn load(&self) -> Result<Option<LedgerIndex>, Error> {
+		match fs::read_to_string(&self.path) {
+			Ok(content) => content.trim().parse().map(Some).map_err(|_| {
+				io::Error::new(
+					io::ErrorKind::InvalidData,
+					format!("invalid ledger index in {}", self.path.display()),
+				)
+				.into()
+			}),
+			Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+			Err(error) => Err(error.into()),
+		}
+	}
+
+	/// Replace the last relayed ledger, through a temporary file so it is never left partial
+	pub fn save(&self, ledger_index: LedgerIndex) -> Result<(), Error> {
+		let temporary = self.path.with_extension("tmp");
+		fs::write(&temporary, format!("{ledger_index}\n"))?;
+		fs::rename(&temporary, &self.path)?;
+		Ok(())
+	}
+}
@@ -0,0 +1,79 @@
+//! Sources of the validated ledgers of the external ledger
+
+use crate::Error;
+use pallet_bridge::offchain::{
+	decode_address, ledger_request, parse_ledger_payments, parse_validated_ledger_index,
+	rpc_result, validated_ledger_request, LedgerPayment, OffchainError,
+};
+use primitives::bridge::LedgerIndex;
+use std::{io::Read, time::Duration};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub trait LedgerSource {
+	/// Index of the latest validated ledger
+	fn validated_ledger_index(&self) -> Result<LedgerIndex, Error>;
+
+	/// Payments to the door account on a validated ledger
+	fn ledger_payments(&self, ledger_index: LedgerIndex) -> Result<Vec<LedgerPayment>, Error>;
+}
+
+/// Node of the external ledger serving its JSON-RPC API
+pub struct JsonRpcLedger {
+	endpoint: String,
+	door_account: Vec<u8>,
+	agent: ureq::Agent,
+}
+
+impl JsonRpcLedger {
+	/// `door_account` is the classic address of the door account
+	pub fn new(endpoint: impl Into<String>, door_account: &str) -> Result<Self, Error> {
+		if decode_address(door_account.as_bytes()).is_none() {
+			return Err(Error::Ledger(format!("invalid door account {door_account}")))
+		}
+		Ok(Self {
+			endpoint: endpoint.into(),
+			door_account: door_account.as_bytes().to_vec(),
+			agent: ureq::AgentBuilder::new().timeout(HTTP_TIMEOUT).build(),
+		})
+	}
+
+	fn call(&self, body: Vec<u8>) -> Result<Vec<u8>, Error> {
+		let response = self
+			.agent
+			.post(&self.endpoint)
+			.set("Content-Type", "application/json")
+			.send_bytes(&body)
+			.map_err(|error| Error::Unavailable(error.to_string()))?;
+		let mut body = Vec::new();
+		response
+			.into_reader()
+			.read_to_end(&mut body)
+			.map_err(|error| Error::Unavailable(error.to_string()))?;
+		Ok(body)
+	}
+}
+
+impl LedgerSource for JsonRpcLedger {
+	fn validated_ledger_index(&self) -> Result<LedgerIndex, Error> {
+		let body = self.call(validated_ledger_request())?;
+		rpc_result(&body)
+			.and_then(|result| parse_validated_ledger_index(&result))
+			.map_err(ledger_error)
+	}
+
+	fn ledger_payments(&self, ledger_index: LedgerIndex) -> Result<Vec<LedgerPayment>, Error> {
+		let body = self.call(ledger_request(ledger_index))?;
+		rpc_result(&body)
+			.and_then(|result| parse_ledger_payments(&result, &self.door_account))
+			.map_err(ledger_error)
+	}
+}
+
+fn ledger_error(error: OffchainError) -> Error {
+	match error {
+		// Validated a little later
+		OffchainError::NotValidated => Error::Unavailable(format!("{error:?}")),
+		_ => Error::Ledger(format!("{error:?}")),
+	}
+}
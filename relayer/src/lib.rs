@@ -0,0 +1,37 @@
+//! Relayer of the payments to the door account on the external ledger
+//!
+//! It follows the validated ledgers of a `LedgerSource` from the one after its `Checkpoint`, and
+//! submits their payments to the node with `submit_transaction` extrinsics signed by its relayer
+//! key. Payments are parsed as the offchain worker of the bridge pallet parses them.
+
+pub mod checkpoint;
+pub mod ledger;
+pub mod node;
+pub mod relayer;
+pub mod signer;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub use checkpoint::Checkpoint;
+pub use ledger::{JsonRpcLedger, LedgerSource};
+pub use node::{Node, RpcNode};
+pub use relayer::{Config, Relayer};
+pub use signer::Signer;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The ledger source or the node cannot be reached, worth retrying
+	#[error("unavailable: {0}")]
+	Unavailable(String),
+	#[error("external ledger error: {0}")]
+	Ledger(String),
+	#[error("node error: {0}")]
+	Node(String),
+	#[error("checkpoint error: {0}")]
+	Checkpoint(#[from] std::io::Error),
+	#[error("invalid relayer key: {0}")]
+	InvalidKey(String),
+}
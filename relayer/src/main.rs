@@ -0,0 +1,80 @@
+//! Bridge relayer daemon.
+
+use bridge_relayer::{Checkpoint, Config, JsonRpcLedger, Relayer, RpcNode, Signer};
+use clap::Parser;
+use std::{path::PathBuf, thread, time::Duration};
+
+/// Relays the payments to the door account on the external ledger to the bridge.
+#[derive(Debug, Parser)]
+struct Cli {
+	/// JSON-RPC URL of the bridge node.
+	#[arg(long, default_value = "http://127.0.0.1:9933")]
+	node_url: String,
+
+	/// JSON-RPC URL of a node of the external ledger.
+	#[arg(long)]
+	ledger_url: String,
+
+	/// Classic address of the door account on the external ledger.
+	#[arg(long)]
+	door_account: String,
+
+	/// Secret URI of the ecdsa relayer key.
+	#[arg(long, env = "RELAYER_SURI", hide_env_values = true)]
+	suri: String,
+
+	/// File holding the last relayed ledger index.
+	#[arg(long, default_value = "relayer.checkpoint")]
+	checkpoint: PathBuf,
+
+	/// Maximum number of ledgers relayed per run.
+	#[arg(long, default_value_t = 10)]
+	ledgers_per_run: u32,
+
+	/// Seconds between two runs.
+	#[arg(long, default_value_t = 4)]
+	interval: u64,
+
+	/// Attempts after the first one when the external ledger or the node is unavailable.
+	#[arg(long, default_value_t = 3)]
+	retries: u32,
+
+	/// Log the extrinsics instead of submitting them, and leave the checkpoint untouched.
+	#[arg(long)]
+	dry_run: bool,
+
+	/// Relay once and exit.
+	#[arg(long)]
+	once: bool,
+}
+
+fn main() -> Result<(), bridge_relayer::Error> {
+	env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+	let cli = Cli::parse();
+
+	let signer = Signer::from_suri(&cli.suri)?;
+	log::info!("relaying as {:?}", signer.account());
+	let config = Config {
+		ledgers_per_run: cli.ledgers_per_run,
+		retries: cli.retries,
+		dry_run: cli.dry_run,
+		..Default::default()
+	};
+	let mut relayer = Relayer::new(
+		JsonRpcLedger::new(cli.ledger_url, &cli.door_account)?,
+		RpcNode::new(cli.node_url),
+		signer,
+		Checkpoint::new(cli.checkpoint),
+		config,
+	);
+
+	loop {
+		match relayer.relay() {
+			Ok(_) if cli.once => return Ok(()),
+			Err(error) if cli.once => return Err(error),
+			Ok(_) => {},
+			Err(error) => log::error!("relay failed: {}", error),
+		}
+		thread::sleep(Duration::from_secs(cli.interval));
+	}
+}
@@ -0,0 +1,168 @@
+use crate::{signer::ChainInfo, Error, LedgerSource, Node};
+use codec::Decode;
+use node_bridge_runtime::{
+	constants::currency::{JUR_ASSET_ID, JUR_CURRENCY_CODE},
+	pallet_bridge::{
+		helpers::{ExternalCurrency, TxData, TxStatus},
+		offchain::LedgerPayment,
+	},
+	AccountId, BalancesConfig, BlockNumber, BridgeConfig, BuildStorage, Executive, GenesisConfig,
+	Hash, Header, Index, Runtime, UncheckedExtrinsic, VERSION,
+};
+use primitives::bridge::{LedgerIndex, TxHash};
+use sp_core::{blake2_256, H160};
+use std::{
+	cell::{Cell, RefCell},
+	collections::BTreeMap,
+};
+
+/// Unix time of the mocked ledgers
+pub const LEDGER_TIME: u64 = 1_600_000_000;
+/// Secret URI of the dev relayer key
+pub const RELAYER_SURI: &str = "//Alice";
+
+pub fn jur_currency() -> ExternalCurrency {
+	ExternalCurrency { code: JUR_CURRENCY_CODE, issuer: H160::from_low_u64_be(1) }
+}
+
+/// Payment of `amount` JUR
+pub fn payment(hash: u64, amount: u128) -> LedgerPayment {
+	LedgerPayment {
+		transaction_hash: TxHash::from_low_u64_be(hash),
+		transaction: TxData::Payment {
+			amount,
+			address: H160::from_low_u64_be(555),
+			currency: jur_currency(),
+		},
+		timestamp: LEDGER_TIME,
+	}
+}
+
+/// Ledger source serving the ledgers given to it
+#[derive(Default)]
+pub struct MockLedger {
+	pub validated: Cell<LedgerIndex>,
+	pub ledgers: RefCell<BTreeMap<LedgerIndex, Vec<LedgerPayment>>>,
+	/// Number of the next requests failing as unavailable
+	pub failures: Cell<u32>,
+	pub requests: RefCell<Vec<LedgerIndex>>,
+}
+
+impl MockLedger {
+	pub fn new(validated: LedgerIndex) -> Self {
+		Self { validated: Cell::new(validated), ..Default::default() }
+	}
+
+	pub fn with_ledger(self, ledger_index: LedgerIndex, payments: Vec<LedgerPayment>) -> Self {
+		self.ledgers.borrow_mut().insert(ledger_index, payments);
+		self
+	}
+
+	fn fail(&self) -> Result<(), Error> {
+		match self.failures.get() {
+			0 => Ok(()),
+			failures => {
+				self.failures.set(failures - 1);
+				Err(Error::Unavailable("mock ledger unavailable".into()))
+			},
+		}
+	}
+}
+
+impl LedgerSource for MockLedger {
+	fn validated_ledger_index(&self) -> Result<LedgerIndex, Error> {
+		self.fail()?;
+		Ok(self.validated.get())
+	}
+
+	fn ledger_payments(&self, ledger_index: LedgerIndex) -> Result<Vec<LedgerPayment>, Error> {
+		self.fail()?;
+		self.requests.borrow_mut().push(ledger_index);
+		Ok(self.ledgers.borrow().get(&ledger_index).cloned().unwrap_or_default())
+	}
+}
+
+/// In-process dev chain, with the native runtime and the dev relayer at genesis
+/// Submitted extrinsics wait in its pool until `produce_block` applies them
+pub struct DevNode {
+	ext: RefCell<sp_io::TestExternalities>,
+	pub pool: RefCell<Vec<Vec<u8>>>,
+}
+
+impl DevNode {
+	pub fn new(relayer: AccountId) -> Self {
+		let storage = GenesisConfig {
+			balances: BalancesConfig { balances: vec![(relayer, 1 << 60)] },
+			bridge: BridgeConfig {
+				relayers: vec![relayer],
+				relayer_threshold: 1,
+				currencies: vec![(jur_currency(), JUR_ASSET_ID)],
+			},
+			..Default::default()
+		}
+		.build_storage()
+		.unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| {
+			let genesis_hash = frame_system::Pallet::<Runtime>::block_hash(0);
+			Executive::initialize_block(&Header::new(
+				1,
+				Default::default(),
+				Default::default(),
+				genesis_hash,
+				Default::default(),
+			));
+			// Set directly, the timestamp inherent would have to match the aura slot
+			pallet_timestamp::Now::<Runtime>::put(LEDGER_TIME * 1000);
+		});
+		Self { ext: RefCell::new(ext), pool: Default::default() }
+	}
+
+	pub fn execute_with<R>(&self, f: impl FnOnce() -> R) -> R {
+		self.ext.borrow_mut().execute_with(f)
+	}
+
+	/// Apply the extrinsics of the pool, panics on invalid or failed ones
+	pub fn produce_block(&self) {
+		for extrinsic in self.pool.borrow_mut().drain(..) {
+			let extrinsic = UncheckedExtrinsic::decode(&mut &extrinsic[..]).unwrap();
+			let result = self.execute_with(|| Executive::apply_extrinsic(extrinsic));
+			assert_eq!(result, Ok(Ok(())));
+		}
+	}
+}
+
+impl Node for DevNode {
+	fn chain_info(&self) -> Result<ChainInfo, Error> {
+		let genesis_hash = self.execute_with(|| frame_system::Pallet::<Runtime>::block_hash(0));
+		Ok(ChainInfo {
+			genesis_hash,
+			spec_version: VERSION.spec_version,
+			transaction_version: VERSION.transaction_version,
+			best_number: 0,
+			best_hash: genesis_hash,
+		})
+	}
+
+	/// Nonce of the chain state, the pool is not counted
+	fn account_nonce(&self, account: &AccountId) -> Result<Index, Error> {
+		Ok(self.execute_with(|| frame_system::Pallet::<Runtime>::account_nonce(account)))
+	}
+
+	fn transaction_status(
+		&self,
+		transaction_hash: TxHash,
+	) -> Result<Option<TxStatus<BlockNumber>>, Error> {
+		Ok(self.execute_with(|| node_bridge_runtime::Bridge::transaction_status(transaction_hash)))
+	}
+
+	fn attested(&self, transaction_hash: TxHash, account: &AccountId) -> Result<bool, Error> {
+		Ok(self.execute_with(|| node_bridge_runtime::Bridge::attested(transaction_hash, account)))
+	}
+
+	fn submit_extrinsic(&self, extrinsic: Vec<u8>) -> Result<Hash, Error> {
+		let hash = blake2_256(&extrinsic).into();
+		self.pool.borrow_mut().push(extrinsic);
+		Ok(hash)
+	}
+}
@@ -0,0 +1,111 @@
+//! Node of the bridge chain the relayer submits extrinsics to
+
+use crate::{signer::ChainInfo, Error};
+use node_bridge_runtime::{pallet_bridge::helpers::TxStatus, AccountId, BlockNumber, Hash, Index};
+use primitives::bridge::TxHash;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use sp_core::Bytes;
+use std::time::Duration;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub trait Node {
+	/// Chain values the extrinsics signed now commit to
+	fn chain_info(&self) -> Result<ChainInfo, Error>;
+
+	/// Nonce of the next extrinsic of `account`, counting the ones in the transaction pool
+	fn account_nonce(&self, account: &AccountId) -> Result<Index, Error>;
+
+	/// Status of a transaction on the bridge, `None` if no relayer submitted it yet
+	fn transaction_status(
+		&self,
+		transaction_hash: TxHash,
+	) -> Result<Option<TxStatus<BlockNumber>>, Error>;
+
+	/// Whether `account` already attested to a transaction, which it can not submit again
+	fn attested(&self, transaction_hash: TxHash, account: &AccountId) -> Result<bool, Error>;
+
+	/// Submit a signed extrinsic to the transaction pool, returns its hash
+	fn submit_extrinsic(&self, extrinsic: Vec<u8>) -> Result<Hash, Error>;
+}
+
+/// Node serving its JSON-RPC API over HTTP
+pub struct RpcNode {
+	endpoint: String,
+	agent: ureq::Agent,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RuntimeVersion {
+	spec_version: u32,
+	transaction_version: u32,
+}
+
+#[derive(Deserialize)]
+struct Header {
+	number: sp_core::U256,
+}
+
+impl RpcNode {
+	pub fn new(endpoint: impl Into<String>) -> Self {
+		Self {
+			endpoint: endpoint.into(),
+			agent: ureq::AgentBuilder::new().timeout(HTTP_TIMEOUT).build(),
+		}
+	}
+
+	fn call<R: DeserializeOwned>(&self, method: &str, params: Value) -> Result<R, Error> {
+		let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+		let mut response: Value = self
+			.agent
+			.post(&self.endpoint)
+			.send_json(request)
+			.map_err(|error| Error::Unavailable(error.to_string()))?
+			.into_json()
+			.map_err(|error| Error::Unavailable(error.to_string()))?;
+		if let Some(error) = response.get("error") {
+			return Err(Error::Node(format!("{method}: {error}")))
+		}
+		serde_json::from_value(response["result"].take())
+			.map_err(|error| Error::Node(format!("{method}: invalid result: {error}")))
+	}
+}
+
+impl Node for RpcNode {
+	fn chain_info(&self) -> Result<ChainInfo, Error> {
+		let genesis_hash = self.call("chain_getBlockHash", json!([0]))?;
+		let version: RuntimeVersion = self.call("state_getRuntimeVersion", json!([]))?;
+		let best_hash: Hash = self.call("chain_getBlockHash", json!([]))?;
+		let header: Header = self.call("chain_getHeader", json!([best_hash]))?;
+		Ok(ChainInfo {
+			genesis_hash,
+			spec_version: version.spec_version,
+			transaction_version: version.transaction_version,
+			best_number: header.number.try_into().map_err(|_| {
+				Error::Node(format!("chain_getHeader: invalid block number {}", header.number))
+			})?,
+			best_hash,
+		})
+	}
+
+	fn account_nonce(&self, account: &AccountId) -> Result<Index, Error> {
+		self.call("system_accountNextIndex", json!([account]))
+	}
+
+	fn transaction_status(
+		&self,
+		transaction_hash: TxHash,
+	) -> Result<Option<TxStatus<BlockNumber>>, Error> {
+		self.call("bridge_transactionStatus", json!([transaction_hash]))
+	}
+
+	fn attested(&self, transaction_hash: TxHash, account: &AccountId) -> Result<bool, Error> {
+		self.call("bridge_attested", json!([transaction_hash, account]))
+	}
+
+	fn submit_extrinsic(&self, extrinsic: Vec<u8>) -> Result<Hash, Error> {
+		self.call("author_submitExtrinsic", json!([Bytes(extrinsic)]))
+	}
+}
@@ -0,0 +1,157 @@
+//! Relay loop, from the ledgers after the checkpoint to the node
+
+use crate::{signer::ChainInfo, Checkpoint, Error, LedgerSource, Node, Signer};
+use codec::Encode;
+use node_bridge_runtime::{pallet_bridge, pallet_bridge::helpers::TxStatus, Index, RuntimeCall};
+use pallet_bridge::offchain::LedgerPayment;
+use primitives::bridge::{LedgerIndex, TxHash};
+use std::{thread, time::Duration};
+
+#[derive(Debug, Clone)]
+pub struct Config {
+	/// Maximum number of ledgers relayed per run
+	pub ledgers_per_run: u32,
+	/// Attempts after the first one when the ledger source or the node is unavailable
+	pub retries: u32,
+	pub retry_delay: Duration,
+	/// Log the extrinsics instead of submitting them, and leave the checkpoint untouched
+	pub dry_run: bool,
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Self {
+			ledgers_per_run: 10,
+			retries: 3,
+			retry_delay: Duration::from_secs(2),
+			dry_run: false,
+		}
+	}
+}
+
+pub struct Relayer<S, N> {
+	source: S,
+	node: N,
+	signer: Signer,
+	checkpoint: Checkpoint,
+	config: Config,
+	/// Nonce of the next extrinsic, tracked between the submissions of a run
+	nonce: Option<Index>,
+}
+
+impl<S: LedgerSource, N: Node> Relayer<S, N> {
+	pub fn new(source: S, node: N, signer: Signer, checkpoint: Checkpoint, config: Config) -> Self {
+		Self { source, node, signer, checkpoint, config, nonce: None }
+	}
+
+	pub fn source(&self) -> &S {
+		&self.source
+	}
+
+	pub fn node(&self) -> &N {
+		&self.node
+	}
+
+	/// Relay the validated ledgers after the checkpoint, or the latest one without a checkpoint
+	/// Returns the hashes of the transactions submitted
+	pub fn relay(&mut self) -> Result<Vec<TxHash>, Error> {
+		// The transaction pool may have dropped extrinsics since the last run
+		self.nonce = None;
+		let validated = self.retry(|relayer| relayer.source.validated_ledger_index())?;
+		let from = match self.checkpoint.load()? {
+			Some(last) => last.saturating_add(1),
+			None => validated,
+		};
+		if from > validated {
+			return Ok(Vec::new())
+		}
+		let to = validated
+			.min(from.saturating_add(self.config.ledgers_per_run.saturating_sub(1).into()));
+
+		let chain = self.retry(|relayer| relayer.node.chain_info())?;
+		let mut relayed = Vec::new();
+		for ledger_index in from..=to {
+			let payments = self.retry(|relayer| relayer.source.ledger_payments(ledger_index))?;
+			for payment in payments {
+				let transaction_hash = payment.transaction_hash;
+				if self.relay_payment(&chain, ledger_index, payment)? {
+					relayed.push(transaction_hash);
+				}
+			}
+			if !self.config.dry_run {
+				self.checkpoint.save(ledger_index)?;
+			}
+		}
+		log::info!("relayed ledgers {}..={}: {} transactions", from, to, relayed.len());
+		Ok(relayed)
+	}
+
+	/// Submit a payment no relayer submitted yet or waiting for the attestation of this one,
+	/// returns whether it was submitted
+	fn relay_payment(
+		&mut self,
+		chain: &ChainInfo,
+		ledger_index: LedgerIndex,
+		payment: LedgerPayment,
+	) -> Result<bool, Error> {
+		let LedgerPayment { transaction_hash, transaction, timestamp } = payment;
+		let status = self.retry(|relayer| relayer.node.transaction_status(transaction_hash))?;
+		if !matches!(status, None | Some(TxStatus::Rejected)) {
+			log::debug!("skipping {:?}, already {:?}", transaction_hash, status);
+			return Ok(false)
+		}
+		let account = self.signer.account();
+		if self.retry(|relayer| relayer.node.attested(transaction_hash, &account))? {
+			log::debug!("skipping {:?}, already attested", transaction_hash);
+			return Ok(false)
+		}
+		let call = RuntimeCall::Bridge(pallet_bridge::Call::submit_transaction {
+			ledger_index,
+			transaction_hash,
+			transaction,
+			timestamp,
+		});
+		if self.config.dry_run {
+			log::info!("dry run, not submitting {:?}", call);
+			return Ok(true)
+		}
+
+		let nonce = match self.nonce {
+			Some(nonce) => nonce,
+			None => self.retry(|relayer| relayer.node.account_nonce(&account))?,
+		};
+		let extrinsic = self.signer.sign_extrinsic(call, nonce, chain).encode();
+		match self.retry(|relayer| relayer.node.submit_extrinsic(extrinsic.clone())) {
+			Ok(hash) => {
+				log::info!("submitted {:?} in extrinsic {:?}", transaction_hash, hash);
+				self.nonce = Some(nonce + 1);
+				Ok(true)
+			},
+			Err(error) => {
+				// Fetched again from the node with the next extrinsic
+				self.nonce = None;
+				Err(error)
+			},
+		}
+	}
+
+	/// Call `f` again while the ledger source or the node is unavailable, up to `retries` times
+	fn retry<R>(&self, mut f: impl FnMut(&Self) -> Result<R, Error>) -> Result<R, Error> {
+		let mut attempt = 0;
+		loop {
+			match f(self) {
+				Err(Error::Unavailable(reason)) if attempt < self.config.retries => {
+					attempt += 1;
+					log::warn!(
+						"unavailable: {}, retry {}/{}",
+						reason,
+						attempt,
+						self.config.retries
+					);
+					thread::sleep(self.config.retry_delay);
+				},
+				result => return result,
+			}
+		}
+	}
+}
@@ -0,0 +1,85 @@
+//! Relayer key signing extrinsics as `BridgeSignature` verifies them
+
+use crate::Error;
+use codec::Encode;
+use node_bridge_runtime::{
+	AccountId, BlockHashCount, BlockNumber, Hash, Index, Runtime, RuntimeCall, SignedExtra,
+	SignedPayload, UncheckedExtrinsic,
+};
+use primitives::signature::{signing_digest, BridgeSignature, BridgeSigner};
+use sp_core::{ecdsa, Pair};
+use sp_runtime::{generic::Era, traits::IdentifyAccount, MultiAddress};
+
+/// Chain values every signed extrinsic commits to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainInfo {
+	pub genesis_hash: Hash,
+	pub spec_version: u32,
+	pub transaction_version: u32,
+	/// Block the mortality period of extrinsics starts from
+	pub best_number: BlockNumber,
+	pub best_hash: Hash,
+}
+
+pub struct Signer {
+	pair: ecdsa::Pair,
+}
+
+impl Signer {
+	/// Key of a secret URI, such as a hex seed or a mnemonic phrase with a derivation path
+	pub fn from_suri(suri: &str) -> Result<Self, Error> {
+		ecdsa::Pair::from_string(suri, None)
+			.map(|pair| Self { pair })
+			.map_err(|error| Error::InvalidKey(format!("{error:?}")))
+	}
+
+	pub fn address(&self) -> BridgeSigner {
+		BridgeSigner::from(self.pair.public())
+	}
+
+	pub fn account(&self) -> AccountId {
+		self.address().into_account()
+	}
+
+	/// Sign the `signing_digest` of `payload`
+	pub fn sign(&self, payload: &[u8]) -> BridgeSignature {
+		self.pair.sign_prehashed(&signing_digest(payload)).into()
+	}
+
+	/// Extrinsic dispatching `call` from the relayer account, valid for half the block hashes kept
+	pub fn sign_extrinsic(
+		&self,
+		call: RuntimeCall,
+		nonce: Index,
+		chain: &ChainInfo,
+	) -> UncheckedExtrinsic {
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(Era::mortal(period, chain.best_number.into())),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+		);
+		let payload = SignedPayload::from_raw(
+			call.clone(),
+			extra.clone(),
+			(
+				(),
+				chain.spec_version,
+				chain.transaction_version,
+				chain.genesis_hash,
+				chain.best_hash,
+				(),
+				(),
+				(),
+			),
+		);
+		let signature = payload.using_encoded(|payload| self.sign(payload));
+		UncheckedExtrinsic::new_signed(call, MultiAddress::Id(self.account()), signature, extra)
+	}
+}
@@ -0,0 +1,192 @@
+use crate::{mock::*, Checkpoint, Config, Error, Relayer, Signer};
+use node_bridge_runtime::{
+	pallet_bridge::helpers::{ProcessAt, TxStatus},
+	Bridge, Runtime, TxChallengePeriod,
+};
+use primitives::{bridge::TxHash, signature::BridgeSignature};
+use sp_runtime::traits::{IdentifyAccount, Verify};
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn relayer(source: MockLedger, dir: &TempDir, config: Config) -> Relayer<MockLedger, DevNode> {
+	let signer = Signer::from_suri(RELAYER_SURI).unwrap();
+	let node = DevNode::new(signer.account());
+	let checkpoint = Checkpoint::new(dir.path().join("relayer.checkpoint"));
+	Relayer::new(source, node, signer, checkpoint, Config { retry_delay: Duration::ZERO, ..config })
+}
+
+/// Status of the payments submitted at the first block of the dev node
+fn pending() -> Option<TxStatus<u32>> {
+	Some(TxStatus::Pending(ProcessAt::Block(1 + TxChallengePeriod::get())))
+}
+
+fn checkpoint(dir: &TempDir) -> Option<u64> {
+	Checkpoint::new(dir.path().join("relayer.checkpoint")).load().unwrap()
+}
+
+fn status(relayer: &Relayer<MockLedger, DevNode>, hash: u64) -> Option<TxStatus<u32>> {
+	relayer
+		.node()
+		.execute_with(|| Bridge::transaction_status(TxHash::from_low_u64_be(hash)))
+}
+
+#[test]
+fn relays_payments_to_the_dev_node() {
+	let dir = tempfile::tempdir().unwrap();
+	let source = MockLedger::new(100).with_ledger(100, vec![payment(1, 1_000_000)]);
+	let mut relayer = relayer(source, &dir, Config::default());
+
+	assert_eq!(relayer.relay().unwrap(), vec![TxHash::from_low_u64_be(1)]);
+	relayer.node().produce_block();
+
+	assert_eq!(status(&relayer, 1), pending());
+	let (ledger_index, transaction, submitter) = relayer.node().execute_with(|| {
+		node_bridge_runtime::pallet_bridge::ProcessTransactionDetails::<Runtime>::get(
+			TxHash::from_low_u64_be(1),
+		)
+		.unwrap()
+	});
+	assert_eq!(ledger_index, 100);
+	assert_eq!(transaction, payment(1, 1_000_000).transaction);
+	assert_eq!(submitter, Signer::from_suri(RELAYER_SURI).unwrap().account());
+	assert_eq!(checkpoint(&dir), Some(100));
+}
+
+#[test]
+fn resumes_after_the_checkpoint() {
+	let dir = tempfile::tempdir().unwrap();
+	Checkpoint::new(dir.path().join("relayer.checkpoint")).save(101).unwrap();
+	let source = MockLedger::new(105)
+		.with_ledger(101, vec![payment(1, 1_000_000)])
+		.with_ledger(103, vec![payment(2, 2_000_000)]);
+	let mut relayer = relayer(source, &dir, Config { ledgers_per_run: 3, ..Default::default() });
+
+	assert_eq!(relayer.relay().unwrap(), vec![TxHash::from_low_u64_be(2)]);
+	assert_eq!(*relayer.source().requests.borrow(), vec![102, 103, 104]);
+	assert_eq!(checkpoint(&dir), Some(104));
+
+	assert_eq!(relayer.relay().unwrap(), vec![]);
+	assert_eq!(*relayer.source().requests.borrow(), vec![102, 103, 104, 105]);
+	assert_eq!(checkpoint(&dir), Some(105));
+
+	// Nothing validated after the checkpoint
+	assert_eq!(relayer.relay().unwrap(), vec![]);
+	assert_eq!(relayer.source().requests.borrow().len(), 4);
+}
+
+#[test]
+fn tracks_nonces_between_submissions() {
+	let dir = tempfile::tempdir().unwrap();
+	let source =
+		MockLedger::new(100).with_ledger(100, vec![payment(1, 1_000_000), payment(2, 2_000_000)]);
+	let mut relayer = relayer(source, &dir, Config::default());
+
+	assert_eq!(relayer.relay().unwrap().len(), 2);
+	assert_eq!(relayer.node().pool.borrow().len(), 2);
+	relayer.node().produce_block();
+
+	assert_eq!(status(&relayer, 1), pending());
+	assert_eq!(status(&relayer, 2), pending());
+	let account = Signer::from_suri(RELAYER_SURI).unwrap().account();
+	assert_eq!(
+		relayer
+			.node()
+			.execute_with(|| frame_system::Pallet::<Runtime>::account_nonce(account)),
+		2
+	);
+}
+
+#[test]
+fn skips_submitted_transactions() {
+	let dir = tempfile::tempdir().unwrap();
+	let source = MockLedger::new(100).with_ledger(100, vec![payment(1, 1_000_000)]);
+	let mut relayer = relayer(source, &dir, Config::default());
+	relayer.relay().unwrap();
+	relayer.node().produce_block();
+
+	// Relayed again from scratch, as with a lost checkpoint
+	std::fs::remove_file(dir.path().join("relayer.checkpoint")).unwrap();
+	relayer
+		.source()
+		.ledgers
+		.borrow_mut()
+		.insert(100, vec![payment(1, 1_000_000), payment(2, 5)]);
+
+	assert_eq!(relayer.relay().unwrap(), vec![TxHash::from_low_u64_be(2)]);
+	assert_eq!(relayer.node().pool.borrow().len(), 1);
+	relayer.node().produce_block();
+	assert_eq!(status(&relayer, 2), pending());
+}
+
+#[test]
+fn skips_attested_transactions() {
+	let dir = tempfile::tempdir().unwrap();
+	let source = MockLedger::new(100).with_ledger(100, vec![payment(1, 1_000_000)]);
+	let mut relayer = relayer(source, &dir, Config::default());
+	relayer
+		.node()
+		.execute_with(|| node_bridge_runtime::pallet_bridge::RelayerThreshold::<Runtime>::put(2));
+	relayer.relay().unwrap();
+	relayer.node().produce_block();
+	// Waiting for the attestation of another relayer
+	assert_eq!(status(&relayer, 1), None);
+
+	std::fs::remove_file(dir.path().join("relayer.checkpoint")).unwrap();
+	assert_eq!(relayer.relay().unwrap(), vec![]);
+	assert!(relayer.node().pool.borrow().is_empty());
+}
+
+#[test]
+fn dry_run_submits_nothing() {
+	let dir = tempfile::tempdir().unwrap();
+	let source = MockLedger::new(100).with_ledger(100, vec![payment(1, 1_000_000)]);
+	let mut relayer = relayer(source, &dir, Config { dry_run: true, ..Default::default() });
+
+	assert_eq!(relayer.relay().unwrap(), vec![TxHash::from_low_u64_be(1)]);
+	assert!(relayer.node().pool.borrow().is_empty());
+	assert_eq!(status(&relayer, 1), None);
+	assert_eq!(checkpoint(&dir), None);
+}
+
+#[test]
+fn retries_unavailable_sources() {
+	let dir = tempfile::tempdir().unwrap();
+	let source = MockLedger::new(100).with_ledger(100, vec![payment(1, 1_000_000)]);
+	source.failures.set(3);
+	let mut relayer = relayer(source, &dir, Config { retries: 3, ..Default::default() });
+
+	assert_eq!(relayer.relay().unwrap(), vec![TxHash::from_low_u64_be(1)]);
+	assert_eq!(relayer.source().failures.get(), 0);
+	assert_eq!(checkpoint(&dir), Some(100));
+}
+
+#[test]
+fn fails_once_retries_are_exhausted() {
+	let dir = tempfile::tempdir().unwrap();
+	let source = MockLedger::new(100).with_ledger(100, vec![payment(1, 1_000_000)]);
+	source.failures.set(3);
+	let mut relayer = relayer(source, &dir, Config { retries: 2, ..Default::default() });
+
+	assert!(matches!(relayer.relay(), Err(Error::Unavailable(_))));
+	assert!(relayer.node().pool.borrow().is_empty());
+	assert_eq!(checkpoint(&dir), None);
+}
+
+#[test]
+fn checkpoint_rejects_invalid_files() {
+	let dir = tempfile::tempdir().unwrap();
+	let path = dir.path().join("relayer.checkpoint");
+	std::fs::write(&path, "not a ledger index").unwrap();
+
+	assert!(matches!(Checkpoint::new(path).load(), Err(Error::Checkpoint(_))));
+}
+
+#[test]
+fn signer_signs_bridge_signatures() {
+	let signer = Signer::from_suri(RELAYER_SURI).unwrap();
+	let signature: BridgeSignature = signer.sign(b"payload");
+
+	assert!(signature.verify(&b"payload"[..], &signer.address().into_account()));
+	assert!(!signature.verify(&b"other payload"[..], &signer.address().into_account()));
+	assert!(matches!(Signer::from_suri("not a secret phrase"), Err(Error::InvalidKey(_))));
+}
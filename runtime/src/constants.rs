@@ -15,4 +15,13 @@ pub mod currency {
 	pub const JUR_NAME: &str = "Jur";
 	pub const JUR_SYMBOL: &str = "JUR";
 	pub const JUR_DECIMALS: u8 = 6;
+	/// "JUR" in the 160-bit currency code format of the external ledger
+	pub const JUR_CURRENCY_CODE: [u8; 20] =
+		[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, b'J', b'U', b'R', 0, 0, 0, 0, 0];
+	/// Issuer of the JUR bridged before currencies were registered, the development issuer
+	/// Set it to the issuer of the chain being upgraded before the bridge storage migration
+	pub const JUR_ISSUER: [u8; 20] = [
+		0x4d, 0xbe, 0x9b, 0xa2, 0xa2, 0xbd, 0x1b, 0xf8, 0xbc, 0xf9, 0xd2, 0xe1, 0xb4, 0xe1, 0xb4,
+		0x4c, 0x6c, 0xb4, 0xdb, 0x5e,
+	];
 }
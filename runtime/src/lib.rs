@@ -3,11 +3,12 @@
 #![recursion_limit = "256"]
 
 pub mod constants; // Make the WASM binary available.
-use constants::currency::{deposit, DOLLARS, JUR_ASSET_ID};
+use constants::currency::{deposit, DOLLARS, JUR_ASSET_ID, JUR_CURRENCY_CODE, JUR_ISSUER};
 
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use codec::Encode;
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
@@ -16,7 +17,10 @@ use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, NumberFor, One},
+	traits::{
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT,
+		Extrinsic as ExtrinsicT, NumberFor, One, SaturatedConversion, Verify,
+	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult,
 };
@@ -35,7 +39,7 @@ pub use frame_support::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
 use frame_system::EnsureRoot;
@@ -49,6 +53,8 @@ pub use sp_runtime::{Perbill, Permill};
 
 /// Import the pallet.
 pub use pallet_bridge;
+use pallet_bridge::helpers::{Challenge, ChallengeExpiry, ExternalCurrency, ProcessAt, TxStatus};
+use primitives::bridge::TxHash;
 pub use primitives::types::TokenId;
 
 /// An index to a block.
@@ -90,6 +96,7 @@ pub mod opaque {
 		pub struct SessionKeys {
 			pub aura: Aura,
 			pub grandpa: Grandpa,
+			pub bridge: Bridge,
 		}
 	}
 }
@@ -106,7 +113,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -307,17 +314,109 @@ impl pallet_assets::Config for Runtime {
 parameter_types! {
 	pub const TxChallengePeriod: u32 = 10 * MINUTES;
 	pub const ClearTxPeriod: u32 = 10 * DAYS;
+	pub const MaxProcessPerBlock: u32 = 100;
+	pub const MaxTransactionsPerBlock: u32 = 1_000;
 	pub const JurAssetId: primitives::types::TokenId = JUR_ASSET_ID;
+	pub const ChallengeBond: Balance = 100 * DOLLARS;
+	pub const ChallengeReward: Balance = 10 * DOLLARS;
+	pub const MaxEvidenceLength: u32 = 1024;
+	pub const ChallengeResolutionPeriod: u32 = 7 * DAYS;
+	pub const ChallengeExpiryPolicy: ChallengeExpiry = ChallengeExpiry::Release;
+	pub const BridgePalletId: PalletId = PalletId(*b"jur/brdg");
+	pub const MaxTimestampDrift: u64 = 5 * 60;
+	pub const MaxTransactionAge: u64 = 24 * 60 * 60;
+	pub const WallClockChallengePeriod: Option<u64> = None;
+	pub const MaxTimedScanPerBlock: u32 = 10 * 60;
+	pub const MaxChallengePeriodTiers: u32 = 10;
+	pub const MaxRelayers: u32 = 32;
+	pub const RelayerMinStake: Balance = 1_000 * DOLLARS;
+	pub const RelayerUnbondingPeriod: u32 = 7 * DAYS;
+	pub const RelayerSlash: Perbill = Perbill::from_percent(50);
+	pub const ReserveTolerance: Perbill = Perbill::from_percent(1);
+	pub BridgeTreasuryAccount: AccountId = PalletId(*b"jur/trsy").into_account_truncating();
+	pub const OffchainLedgersPerRun: u32 = 10;
+	pub const OffchainTransactionsPerRun: u32 = 20;
+	/// Currency of the payments submitted before currencies were registered
+	pub LegacyJurCurrency: ExternalCurrency =
+		ExternalCurrency { code: JUR_CURRENCY_CODE, issuer: JUR_ISSUER.into() };
 }
 
 impl pallet_bridge::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Assets = Assets;
 	type ApproveOrigin = EnsureRoot<AccountId>;
+	type PauseOrigin = EnsureRoot<AccountId>;
 	type AssetId = JurAssetId;
 	type ChallengePeriod = TxChallengePeriod;
 	type ClearTxPeriod = ClearTxPeriod;
+	type MaxProcessPerBlock = MaxProcessPerBlock;
+	type MaxTransactionsPerBlock = MaxTransactionsPerBlock;
 	type UnixTime = Timestamp;
+	type MaxTimestampDrift = MaxTimestampDrift;
+	type MaxTransactionAge = MaxTransactionAge;
+	type MaxChallengePeriodTiers = MaxChallengePeriodTiers;
+	type WallClockChallengePeriod = WallClockChallengePeriod;
+	type MaxTimedScanPerBlock = MaxTimedScanPerBlock;
+	type Currency = Balances;
+	type ChallengeBond = ChallengeBond;
+	type ChallengeReward = ChallengeReward;
+	type MaxEvidenceLength = MaxEvidenceLength;
+	type ChallengeResolutionPeriod = ChallengeResolutionPeriod;
+	type ChallengeExpiryPolicy = ChallengeExpiryPolicy;
+	type WeightInfo = pallet_bridge::weights::SubstrateWeight<Runtime>;
+	type PalletId = BridgePalletId;
+	type MaxRelayers = MaxRelayers;
+	type RelayerMinStake = RelayerMinStake;
+	type RelayerUnbondingPeriod = RelayerUnbondingPeriod;
+	type RelayerSlash = RelayerSlash;
+	type ReserveTolerance = ReserveTolerance;
+	type TreasuryAccount = BridgeTreasuryAccount;
+	type AuthorityId = pallet_bridge::crypto::RelayerAuthId;
+	type OffchainLedgersPerRun = OffchainLedgersPerRun;
+	type OffchainTransactionsPerRun = OffchainTransactionsPerRun;
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = RuntimeCall;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(RuntimeCall, <UncheckedExtrinsic as ExtrinsicT>::SignaturePayload)> {
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		let current_block = System::block_number().saturated_into::<u64>().saturating_sub(1);
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (sp_runtime::MultiAddress::Id(account), signature, extra)))
+	}
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -365,6 +464,8 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations run on the next runtime upgrade.
+pub type Migrations = (pallet_bridge::migrations::v1::MigrateToV1<Runtime, LegacyJurCurrency>,);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -372,6 +473,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -538,6 +640,24 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_bridge_runtime_api::BridgeApi<Block, AccountId, BlockNumber> for Runtime {
+		fn transaction_status(transaction_hash: TxHash) -> Option<TxStatus<BlockNumber>> {
+			Bridge::transaction_status(transaction_hash)
+		}
+		fn pending_transactions() -> Vec<(TxHash, ProcessAt<BlockNumber>)> {
+			Bridge::pending_transactions()
+		}
+		fn open_challenges() -> Vec<(TxHash, Challenge<AccountId, BlockNumber, Vec<u8>>)> {
+			Bridge::open_challenges()
+		}
+		fn attested(transaction_hash: TxHash, relayer: AccountId) -> bool {
+			Bridge::attested(transaction_hash, &relayer)
+		}
+		fn relayers() -> Vec<AccountId> {
+			Bridge::relayers()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (